@@ -0,0 +1,34 @@
+use cosmwasm_std::{Addr, Storage};
+use cw_storage_plus::Map;
+
+use crate::error::ContractError;
+use crate::msg::{AccessListKind, AccessMode};
+use crate::state::{ALLOWLIST, DENYLIST};
+
+/// Storage of the `kind` access list
+pub fn list(kind: AccessListKind) -> Map<'static, &'static Addr, ()> {
+    match kind {
+        AccessListKind::Allow => ALLOWLIST,
+        AccessListKind::Deny => DENYLIST,
+    }
+}
+
+/// Fails if `donor` may not donate in the current access mode
+pub fn ensure_allowed(
+    storage: &dyn Storage,
+    mode: AccessMode,
+    donor: &Addr,
+) -> Result<(), ContractError> {
+    let allowed = match mode {
+        AccessMode::Open => true,
+        AccessMode::Allowlist => ALLOWLIST.has(storage, donor),
+        AccessMode::Denylist => !DENYLIST.has(storage, donor),
+    };
+
+    if !allowed {
+        return Err(ContractError::DonorNotAllowed {
+            donor: donor.to_string(),
+        });
+    }
+    Ok(())
+}
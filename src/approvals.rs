@@ -0,0 +1,100 @@
+use cosmwasm_std::{Addr, Api, BlockInfo, Coin, StdResult, Storage, Uint128};
+
+use crate::contract::add_coins;
+use crate::error::ContractError;
+use crate::msg::{ApprovalPolicy, PendingWithdrawalStatus};
+use crate::state::{LimitWindow, PendingWithdrawal, CONFIG, LIMIT_WINDOW};
+
+/// Validates the approvers and the threshold, returning the policy with normalized addresses
+pub fn validate(api: &dyn Api, policy: ApprovalPolicy) -> Result<ApprovalPolicy, ContractError> {
+    let mut approvers: Vec<String> = vec![];
+    for approver in policy.approvers {
+        let approver = api.addr_validate(&approver)?.into_string();
+        if approvers.contains(&approver) {
+            return Err(ContractError::DuplicateApprover { address: approver });
+        }
+        approvers.push(approver);
+    }
+
+    if policy.threshold == 0 || policy.threshold as usize > approvers.len() {
+        return Err(ContractError::InvalidApprovalThreshold {
+            threshold: policy.threshold,
+            approvers: approvers.len() as u32,
+        });
+    }
+
+    Ok(ApprovalPolicy {
+        approvers,
+        ..policy
+    })
+}
+
+/// Whether any coin of `amount` is above its limit, denoms without a limit always are
+pub fn exceeds_limit(policy: &ApprovalPolicy, amount: &[Coin]) -> bool {
+    amount.iter().any(|coin| {
+        let limit = policy
+            .limits
+            .iter()
+            .find(|limit| limit.denom == coin.denom)
+            .map_or(Uint128::zero(), |limit| limit.amount);
+        coin.amount > limit
+    })
+}
+
+/// Approval policy if `amount` together with the direct withdrawals of the current window exceeds
+/// its limits, otherwise `amount` is counted as withdrawn in the window
+pub fn exceeded_policy(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    amount: &[Coin],
+) -> StdResult<Option<ApprovalPolicy>> {
+    let Some(policy) = CONFIG.load(storage)?.approval_policy else {
+        return Ok(None);
+    };
+
+    let mut window = LIMIT_WINDOW
+        .may_load(storage)?
+        .filter(|window| !window.ends.is_expired(block))
+        .unwrap_or_else(|| LimitWindow {
+            ends: policy.window.after(block),
+            withdrawn: vec![],
+        });
+    add_coins(&mut window.withdrawn, amount);
+    if exceeds_limit(&policy, &window.withdrawn) {
+        return Ok(Some(policy));
+    }
+
+    LIMIT_WINDOW.save(storage, &window)?;
+    Ok(None)
+}
+
+pub fn ensure_approver(policy: &ApprovalPolicy, sender: &Addr) -> Result<(), ContractError> {
+    if !policy.approvers.iter().any(|approver| sender == approver) {
+        return Err(ContractError::NotApprover {});
+    }
+    Ok(())
+}
+
+/// Approvals given by members of the current approver set
+pub fn valid_approvals(policy: &ApprovalPolicy, pending: &PendingWithdrawal) -> u32 {
+    pending
+        .approvals
+        .iter()
+        .filter(|approval| {
+            policy
+                .approvers
+                .iter()
+                .any(|approver| *approval == approver)
+        })
+        .count() as u32
+}
+
+pub fn status(pending: &PendingWithdrawal, block: &BlockInfo) -> PendingWithdrawalStatus {
+    if pending.executed {
+        PendingWithdrawalStatus::Executed
+    } else if pending.expires.is_expired(block) {
+        PendingWithdrawalStatus::Expired
+    } else {
+        PendingWithdrawalStatus::Open
+    }
+}
@@ -0,0 +1,77 @@
+use cosmwasm_std::{Addr, Api, Coin, Uint128};
+
+use crate::contract::add_coins;
+use crate::error::ContractError;
+use crate::msg::{Beneficiary, TOTAL_WEIGHT_BPS};
+use crate::state::Beneficiaries;
+
+/// Validates the addresses and checks the weights sum up to `TOTAL_WEIGHT_BPS`
+pub fn validate(
+    api: &dyn Api,
+    beneficiaries: Vec<Beneficiary>,
+    remainder: &str,
+) -> Result<Beneficiaries, ContractError> {
+    let mut shares: Vec<(Addr, u64)> = vec![];
+    for beneficiary in beneficiaries {
+        let address = api.addr_validate(&beneficiary.address)?;
+        if shares.iter().any(|(addr, _)| *addr == address) {
+            return Err(ContractError::DuplicateBeneficiary {
+                address: address.into_string(),
+            });
+        }
+        shares.push((address, beneficiary.weight_bps));
+    }
+
+    let total = shares
+        .iter()
+        .try_fold(0u64, |total, (_, weight)| total.checked_add(*weight))
+        .unwrap_or(u64::MAX);
+    if total != TOTAL_WEIGHT_BPS {
+        return Err(ContractError::InvalidWeights { total });
+    }
+
+    Ok(Beneficiaries {
+        shares,
+        remainder: api.addr_validate(remainder)?,
+    })
+}
+
+/// Splits `balance` by weight, the rounding dust of every denom goes to the remainder address.
+/// Zero amounts are kept, they are skipped when paying out.
+pub fn split(beneficiaries: &Beneficiaries, balance: &[Coin]) -> Vec<(Addr, Vec<Coin>)> {
+    let mut payouts: Vec<(Addr, Vec<Coin>)> = beneficiaries
+        .shares
+        .iter()
+        .map(|(address, _)| (address.clone(), vec![]))
+        .collect();
+    let mut dust = vec![];
+
+    for coin in balance {
+        let mut paid = Uint128::zero();
+        for ((_, weight), (_, payout)) in beneficiaries.shares.iter().zip(&mut payouts) {
+            let amount = coin.amount.multiply_ratio(*weight, TOTAL_WEIGHT_BPS);
+            paid += amount;
+            add_coins(
+                payout,
+                &[Coin {
+                    denom: coin.denom.clone(),
+                    amount,
+                }],
+            );
+        }
+        dust.push(Coin {
+            denom: coin.denom.clone(),
+            amount: coin.amount - paid,
+        });
+    }
+
+    match payouts
+        .iter_mut()
+        .find(|(address, _)| *address == beneficiaries.remainder)
+    {
+        Some((_, payout)) => add_coins(payout, &dust),
+        None => payouts.push((beneficiaries.remainder.clone(), dust)),
+    }
+
+    payouts
+}
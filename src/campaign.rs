@@ -0,0 +1,128 @@
+use cosmwasm_std::{Addr, BlockInfo, Coin, Order, StdResult, Storage};
+
+use crate::contract::add_coins;
+use crate::contract::exec::CW20_DENOM_PREFIX;
+use crate::error::ContractError;
+use crate::msg::{Campaign, CampaignPhase};
+use crate::state::{ACCEPTED_DENOMS, CAMPAIGN, CAMPAIGN_RAISED, CONTRIBUTIONS};
+
+/// Fails if the campaign could never open or its goal could never be reached. Native goal denoms
+/// have to be accepted, cw20 tokens can be added after instantiation.
+pub fn validate(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    campaign: &Campaign,
+) -> Result<(), ContractError> {
+    let invalid = |reason: &str| ContractError::InvalidCampaign {
+        reason: reason.to_owned(),
+    };
+
+    if campaign.end.is_triggered(block) {
+        return Err(ContractError::CampaignEnded {});
+    }
+    if campaign
+        .start
+        .is_some_and(|start| start.partial_cmp(&campaign.end).is_some_and(|o| o.is_ge()))
+    {
+        return Err(invalid("start has to be before the end"));
+    }
+    if campaign.goal.is_empty() || campaign.goal.iter().any(|coin| coin.amount.is_zero()) {
+        return Err(invalid("goal has to be above zero"));
+    }
+    for coin in &campaign.goal {
+        if !coin.denom.starts_with(CW20_DENOM_PREFIX) && !ACCEPTED_DENOMS.has(storage, &coin.denom)
+        {
+            return Err(invalid(&format!(
+                "goal denom {} is not accepted",
+                coin.denom
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Progress of the campaign at `block`, `None` if there is no campaign
+pub fn phase(storage: &dyn Storage, block: &BlockInfo) -> StdResult<Option<CampaignPhase>> {
+    let Some(campaign) = CAMPAIGN.may_load(storage)? else {
+        return Ok(None);
+    };
+
+    let phase = if campaign
+        .start
+        .is_some_and(|start| !start.is_triggered(block))
+    {
+        CampaignPhase::NotStarted
+    } else if !campaign.end.is_triggered(block) {
+        CampaignPhase::Open
+    } else if goal_reached(storage, &campaign)? {
+        CampaignPhase::Succeeded
+    } else {
+        CampaignPhase::Failed
+    };
+
+    Ok(Some(phase))
+}
+
+pub fn goal_reached(storage: &dyn Storage, campaign: &Campaign) -> StdResult<bool> {
+    for goal in &campaign.goal {
+        let raised = CAMPAIGN_RAISED
+            .may_load(storage, &goal.denom)?
+            .unwrap_or_default();
+        if raised < goal.amount {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Fails unless donations are accepted at `block`.
+pub fn ensure_open(storage: &dyn Storage, block: &BlockInfo) -> Result<(), ContractError> {
+    match phase(storage, block)? {
+        None | Some(CampaignPhase::Open) => Ok(()),
+        Some(CampaignPhase::NotStarted) => Err(ContractError::CampaignNotStarted {}),
+        Some(CampaignPhase::Succeeded | CampaignPhase::Failed) => {
+            Err(ContractError::CampaignEnded {})
+        }
+    }
+}
+
+/// Fails unless the funds can be withdrawn at `block`.
+pub fn ensure_withdrawable(storage: &dyn Storage, block: &BlockInfo) -> Result<(), ContractError> {
+    match phase(storage, block)? {
+        None | Some(CampaignPhase::Succeeded) => Ok(()),
+        Some(CampaignPhase::NotStarted | CampaignPhase::Open) => {
+            Err(ContractError::CampaignNotFinished {})
+        }
+        Some(CampaignPhase::Failed) => Err(ContractError::CampaignGoalNotReached {}),
+    }
+}
+
+/// Adds a donation to the campaign progress and to the refundable contribution of `donor`.
+pub fn record(storage: &mut dyn Storage, donor: &Addr, funds: &[Coin]) -> StdResult<()> {
+    if !CAMPAIGN.exists(storage) || funds.is_empty() {
+        return Ok(());
+    }
+
+    for coin in funds {
+        CAMPAIGN_RAISED.update(storage, &coin.denom, |raised| -> StdResult<_> {
+            Ok(raised.unwrap_or_default() + coin.amount)
+        })?;
+    }
+
+    CONTRIBUTIONS.update(storage, donor, |contribution| -> StdResult<_> {
+        let mut contribution = contribution.unwrap_or_default();
+        add_coins(&mut contribution, funds);
+        Ok(contribution)
+    })?;
+
+    Ok(())
+}
+
+pub fn raised(storage: &dyn Storage) -> StdResult<Vec<Coin>> {
+    CAMPAIGN_RAISED
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+        .collect()
+}
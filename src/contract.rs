@@ -1,38 +1,786 @@
-use crate::state::{COUNTER, MINIMAL_DONATION, OWNER};
-use cosmwasm_std::{DepsMut, MessageInfo, Response, StdResult};
+use crate::error::ContractError;
+use crate::state::{
+    Config, CounterChange, CounterState, Fee, ACCEPTED_DENOMS, CAMPAIGN, CONFIG, COUNTERS,
+    COUNTER_HISTORY, FEE, OWNER,
+};
+use crate::{approvals, campaign, fees, governance, rate_limit, rewards};
+use cosmwasm_std::{Addr, Coin, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage};
+use cw2::set_contract_version;
+use semver::Version;
 
-use crate::msg::InstantiateMsg;
+use crate::msg::{
+    ChangeCause, InstantiateMsg, MigrateMsg, DEFAULT_COUNTER, DEFAULT_MAX_MEMO_LENGTH,
+};
 
-pub fn instantiate(deps: DepsMut, info: MessageInfo, msg: InstantiateMsg) -> StdResult<Response> {
-    COUNTER.save(deps.storage, &msg.counter)?;
-    MINIMAL_DONATION.save(deps.storage, &msg.minimal_donation)?;
+pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    rewards::validate(&msg.milestones)?;
+    if let Some(governance) = &msg.governance {
+        governance::validate(governance)?;
+    }
+    if let Some(limit) = &msg.rate_limit {
+        rate_limit::validate(limit)?;
+    }
+    let approval_policy = msg
+        .approval_policy
+        .map(|policy| approvals::validate(deps.api, policy))
+        .transpose()?;
+    let reward_collection = msg
+        .reward_collection
+        .map(|collection| deps.api.addr_validate(&collection))
+        .transpose()?;
+
+    let fee = Fee {
+        fee_bps: msg.fee_bps,
+        max_fee_bps: msg.max_fee_bps.unwrap_or(msg.fee_bps),
+        collector: msg
+            .fee_collector
+            .map(|collector| deps.api.addr_validate(&collector))
+            .transpose()?,
+        admin: msg
+            .fee_admin
+            .map(|admin| deps.api.addr_validate(&admin))
+            .transpose()?,
+    };
+    fees::validate(&fee)?;
+    FEE.save(deps.storage, &fee)?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            strict: msg.strict,
+            refund: msg.refund,
+            reward_collection,
+            milestones: msg.milestones,
+            governance: msg.governance,
+            approval_policy,
+            access_mode: msg.access_mode,
+            rate_limit: msg.rate_limit,
+            max_memo_length: msg.max_memo_length.unwrap_or(DEFAULT_MAX_MEMO_LENGTH),
+        },
+    )?;
+    save_counter(
+        deps.storage,
+        env.block.height,
+        DEFAULT_COUNTER,
+        &CounterState {
+            value: msg.counter,
+            minimal_donation: None,
+            owner: None,
+        },
+        ChangeCause::Create,
+        Some(&info.sender),
+    )?;
+
+    for coin in std::iter::once(&msg.minimal_donation).chain(&msg.accepted_denoms) {
+        if ACCEPTED_DENOMS.has(deps.storage, &coin.denom) {
+            return Err(ContractError::DuplicateDenom {
+                denom: coin.denom.clone(),
+            });
+        }
+        ACCEPTED_DENOMS.save(deps.storage, &coin.denom, &coin.amount)?;
+    }
+
+    if let Some(campaign) = &msg.campaign {
+        campaign::validate(deps.storage, &env.block, campaign)?;
+        CAMPAIGN.save(deps.storage, campaign)?;
+    }
+
+    // The owner holds every role implicitly, so they move with the ownership
     OWNER.save(deps.storage, &info.sender)?;
     Ok(Response::new())
 }
 
+/// Adds `funds` to `total`, merging coins of the same denom
+pub fn add_coins(total: &mut Vec<Coin>, funds: &[Coin]) {
+    for coin in funds {
+        match total.iter_mut().find(|c| c.denom == coin.denom) {
+            Some(total) => total.amount += coin.amount,
+            None => total.push(coin.clone()),
+        }
+    }
+}
+
+/// Stores the counter state and records the change in the counter history
+pub fn save_counter(
+    storage: &mut dyn Storage,
+    height: u64,
+    counter_id: &str,
+    state: &CounterState,
+    cause: ChangeCause,
+    sender: Option<&Addr>,
+) -> StdResult<()> {
+    COUNTERS.save(storage, counter_id, state, height)?;
+
+    let id = COUNTER_HISTORY
+        .prefix(counter_id)
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(0, |last| last + 1);
+    COUNTER_HISTORY.save(
+        storage,
+        (counter_id, id),
+        &CounterChange {
+            height,
+            value: state.value,
+            cause,
+            sender: sender.cloned(),
+        },
+    )
+}
+
+pub fn migrate(mut deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored = migration::stored_version(deps.as_ref())?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidContractName {
+            expected: CONTRACT_NAME.to_owned(),
+            actual: stored.contract,
+        });
+    }
+
+    let from: Version = stored.version.parse()?;
+    let to: Version = CONTRACT_VERSION.parse()?;
+    if from > to {
+        return Err(ContractError::CannotDowngrade {
+            current: stored.version,
+            new: CONTRACT_VERSION.to_owned(),
+        });
+    }
+
+    if from < Version::new(0, 2, 0) {
+        migration::from_v0_1(deps.branch(), &env)?;
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION);
+
+    Ok(resp)
+}
+
+mod migration {
+    use cosmwasm_std::{Deps, DepsMut, Env, StdResult};
+    use cw2::{get_contract_version, ContractVersion};
+
+    use super::{save_counter, CONTRACT_NAME};
+    use crate::msg::{ChangeCause, DEFAULT_COUNTER};
+    use crate::state::{
+        CounterState, ACCEPTED_DENOMS, CONFIG, LEGACY_COUNTER, LEGACY_MINIMAL_DONATION, OWNER,
+    };
+
+    /// Version of the releases which predate cw2 version tracking.
+    const LEGACY_VERSION: &str = "0.1.0";
+
+    pub fn stored_version(deps: Deps) -> StdResult<ContractVersion> {
+        match get_contract_version(deps.storage) {
+            Ok(version) => Ok(version),
+            Err(err) => {
+                // `0.1.0` never stored cw2 info, but it always had an owner.
+                if OWNER.may_load(deps.storage)?.is_none() {
+                    return Err(err);
+                }
+
+                Ok(ContractVersion {
+                    contract: CONTRACT_NAME.to_owned(),
+                    version: LEGACY_VERSION.to_owned(),
+                })
+            }
+        }
+    }
+
+    pub fn from_v0_1(deps: DepsMut, env: &Env) -> StdResult<()> {
+        // Roles were introduced in `0.2.0` - the owner holds all of them implicitly.
+        // The single minimal donation became the first of the accepted denoms.
+        let minimal_donation = LEGACY_MINIMAL_DONATION.load(deps.storage)?;
+        ACCEPTED_DENOMS.save(
+            deps.storage,
+            &minimal_donation.denom,
+            &minimal_donation.amount,
+        )?;
+        LEGACY_MINIMAL_DONATION.remove(deps.storage);
+
+        // The single counter became the default one of the named counters.
+        let value = LEGACY_COUNTER.load(deps.storage)?;
+        save_counter(
+            deps.storage,
+            env.block.height,
+            DEFAULT_COUNTER,
+            &CounterState {
+                value,
+                minimal_donation: None,
+                owner: None,
+            },
+            ChangeCause::Migrate,
+            None,
+        )?;
+        LEGACY_COUNTER.remove(deps.storage);
+
+        CONFIG.save(deps.storage, &Default::default())?;
+
+        Ok(())
+    }
+}
+
 pub mod query {
-    use crate::msg::ValueResp;
-    use crate::state::COUNTER;
-    use cosmwasm_std::{Deps, StdResult};
+    use crate::msg::{
+        AcceptedDenomsResp, AccessListKind, AccessListResp, BeneficiariesResp, BeneficiaryResp,
+        CampaignResp, ConfigResp, CounterResp, CountersResp, Cw20TokensResp, DonationResp,
+        DonationsResp, DonorResp, DonorsResp, FeeResp, HasRoleResp, HistoryEntry, HistoryResp,
+        IbcChannelsResp, Operation, OwnerResp, PausedOperation, PendingWithdrawalResp,
+        PendingWithdrawalsResp, ProposalResp, ProposalsResp, Role, RoleMembers, RolesResp,
+        StatsResp, StatusResp, ValueResp, VoteResp, VotesResp, WithdrawalResp, WithdrawalsResp,
+        DEFAULT_COUNTER,
+    };
+    use crate::ownership;
+    use crate::state::{
+        CounterState, Donation, DonorRecord, PendingWithdrawal, Proposal, ACCEPTED_DENOMS,
+        BENEFICIARIES, CAMPAIGN, CONFIG, COUNTERS, COUNTER_HISTORY, CW20_TOKENS, DONORS, FEE,
+        FEES_COLLECTED, IBC_CHANNELS, OWNER, PAUSED, PENDING_OWNER, PENDING_WITHDRAWALS, PROPOSALS,
+        ROLES, VOTES, WITHDRAWALS,
+    };
+    use crate::{access, approvals, campaign, governance, state, stats};
+    use cosmwasm_std::{Addr, BlockInfo, Coin, Deps, Env, Order, StdError, StdResult};
+    use cw20::Cw20CoinVerified;
+    use cw_storage_plus::Bound;
 
-    pub fn value(deps: Deps) -> StdResult<ValueResp> {
-        let value = COUNTER.load(deps.storage)?;
+    const DEFAULT_LIMIT: u32 = 10;
+    const MAX_LIMIT: u32 = 30;
+
+    pub fn value(deps: Deps, counter_id: Option<String>) -> StdResult<ValueResp> {
+        let counter_id = counter_id.as_deref().unwrap_or(DEFAULT_COUNTER);
+        let value = COUNTERS.load(deps.storage, counter_id)?.value;
+        Ok(ValueResp { value })
+    }
+
+    pub fn value_at(deps: Deps, counter_id: Option<String>, height: u64) -> StdResult<ValueResp> {
+        let counter_id = counter_id.as_deref().unwrap_or(DEFAULT_COUNTER);
+        let not_found = || StdError::not_found(format!("counter {counter_id} at height {height}"));
+        // Snapshots hold the state from the beginning of a block.
+        let value = COUNTERS
+            .may_load_at_height(
+                deps.storage,
+                counter_id,
+                height.checked_add(1).ok_or_else(not_found)?,
+            )?
+            .ok_or_else(not_found)?
+            .value;
         Ok(ValueResp { value })
     }
 
+    pub fn history(
+        deps: Deps,
+        counter_id: Option<String>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<HistoryResp> {
+        let counter_id = counter_id.as_deref().unwrap_or(DEFAULT_COUNTER);
+        let start = start_after.map(Bound::exclusive);
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        let entries = COUNTER_HISTORY
+            .prefix(counter_id)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(id, change)| HistoryEntry {
+                    id,
+                    height: change.height,
+                    value: change.value,
+                    cause: change.cause,
+                    sender: change.sender,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(HistoryResp { entries })
+    }
+
+    pub fn counters(
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<CountersResp> {
+        let start = start_after.as_deref().map(Bound::exclusive);
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        let counters = COUNTERS
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(counter_id, state)| counter_resp(counter_id, state)))
+            .collect::<StdResult<_>>()?;
+
+        Ok(CountersResp { counters })
+    }
+
+    pub fn owner(deps: Deps) -> StdResult<OwnerResp> {
+        let owner = OWNER.may_load(deps.storage)?;
+        let pending = PENDING_OWNER.may_load(deps.storage)?;
+
+        Ok(OwnerResp {
+            owner,
+            pending_owner: pending.as_ref().map(|p| p.owner.clone()),
+            pending_expiry: pending.map(|p| p.expiry),
+        })
+    }
+
+    pub fn roles(deps: Deps) -> StdResult<RolesResp> {
+        let roles = Role::ALL
+            .into_iter()
+            .map(|role| {
+                let members = ROLES
+                    .prefix(role.as_str())
+                    .keys(deps.storage, None, None, Order::Ascending)
+                    .collect::<StdResult<_>>()?;
+                Ok(RoleMembers { role, members })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(RolesResp {
+            owner: OWNER.may_load(deps.storage)?,
+            roles,
+        })
+    }
+
+    pub fn has_role(deps: Deps, role: Role, address: String) -> StdResult<HasRoleResp> {
+        let address = deps.api.addr_validate(&address)?;
+        let has_role = ownership::has_role(deps.storage, role, &address)?;
+        Ok(HasRoleResp { has_role })
+    }
+
+    pub fn donor(deps: Deps, address: String) -> StdResult<DonorResp> {
+        let address = deps.api.addr_validate(&address)?;
+        let record = DONORS.load(deps.storage, &address)?;
+        Ok(donor_resp(address, record))
+    }
+
+    pub fn donors(
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<DonorsResp> {
+        let start_after = start_after
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?;
+        let start = start_after.as_ref().map(Bound::exclusive);
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        let donors = DONORS
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(address, record)| donor_resp(address, record)))
+            .collect::<StdResult<_>>()?;
+
+        Ok(DonorsResp { donors })
+    }
+
+    pub fn donations(
+        deps: Deps,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<DonationsResp> {
+        let start = start_after.map(Bound::exclusive);
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        let donations = state::donations()
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(id, donation)| donation_resp(id, donation)))
+            .collect::<StdResult<_>>()?;
+
+        Ok(DonationsResp { donations })
+    }
+
+    pub fn donations_by(
+        deps: Deps,
+        donor: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<DonationsResp> {
+        let donor = deps.api.addr_validate(&donor)?;
+        let start = start_after.map(Bound::exclusive);
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        let donations = state::donations()
+            .idx
+            .donor
+            .prefix(donor)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(id, donation)| donation_resp(id, donation)))
+            .collect::<StdResult<_>>()?;
+
+        Ok(DonationsResp { donations })
+    }
+
+    fn donation_resp(id: u64, donation: Donation) -> DonationResp {
+        DonationResp {
+            id,
+            donor: donation.donor,
+            counter_id: donation.counter_id,
+            amount: donation.amount,
+            counted: donation.counted,
+            height: donation.height,
+            memo: donation.memo,
+            memo_hidden: donation.memo_hidden,
+        }
+    }
+
+    pub fn stats(deps: Deps) -> StdResult<StatsResp> {
+        let stats = stats::load(deps.storage)?;
+
+        Ok(StatsResp {
+            counted_donations: stats.counted_donations,
+            uncounted_donations: stats.uncounted_donations,
+            unique_donors: stats.unique_donors,
+            total_received: stats.total_received,
+            total_withdrawn: stats.total_withdrawn,
+            last_donation_height: stats.last_donation_height,
+            last_withdrawal_height: stats.last_withdrawal_height,
+        })
+    }
+
+    pub fn access_list(
+        deps: Deps,
+        list: AccessListKind,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AccessListResp> {
+        let start_after = start_after
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?;
+        let start = start_after.as_ref().map(Bound::exclusive);
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        let addresses = access::list(list)
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .collect::<StdResult<_>>()?;
+
+        Ok(AccessListResp {
+            mode: CONFIG.load(deps.storage)?.access_mode,
+            addresses,
+        })
+    }
+
+    pub fn campaign(deps: Deps, env: Env) -> StdResult<CampaignResp> {
+        Ok(CampaignResp {
+            campaign: CAMPAIGN.may_load(deps.storage)?,
+            phase: campaign::phase(deps.storage, &env.block)?,
+            raised: campaign::raised(deps.storage)?,
+        })
+    }
+
+    pub fn withdrawals(
+        deps: Deps,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<WithdrawalsResp> {
+        let start = start_after.map(Bound::exclusive);
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        let withdrawals = WITHDRAWALS
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(id, record)| WithdrawalResp {
+                    id,
+                    receiver: record.receiver,
+                    amount: record.amount,
+                    height: record.height,
+                    outcome: record.outcome,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(WithdrawalsResp { withdrawals })
+    }
+
+    pub fn beneficiaries(deps: Deps) -> StdResult<BeneficiariesResp> {
+        let Some(beneficiaries) = BENEFICIARIES.may_load(deps.storage)? else {
+            return Ok(BeneficiariesResp {
+                beneficiaries: vec![],
+                remainder: None,
+            });
+        };
+
+        Ok(BeneficiariesResp {
+            beneficiaries: beneficiaries
+                .shares
+                .into_iter()
+                .map(|(address, weight_bps)| BeneficiaryResp {
+                    address,
+                    weight_bps,
+                })
+                .collect(),
+            remainder: Some(beneficiaries.remainder),
+        })
+    }
+
+    pub fn fee(deps: Deps) -> StdResult<FeeResp> {
+        let fee = FEE.may_load(deps.storage)?.unwrap_or_default();
+        let collected = FEES_COLLECTED
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+            .collect::<StdResult<_>>()?;
+
+        Ok(FeeResp {
+            fee_bps: fee.fee_bps,
+            max_fee_bps: fee.max_fee_bps,
+            fee_collector: fee.collector,
+            fee_admin: fee.admin,
+            collected,
+        })
+    }
+
+    pub fn ibc_channels(deps: Deps) -> StdResult<IbcChannelsResp> {
+        let channels = IBC_CHANNELS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?;
+
+        Ok(IbcChannelsResp { channels })
+    }
+
+    pub fn config(deps: Deps) -> StdResult<ConfigResp> {
+        let config = CONFIG.load(deps.storage)?;
+        let owner = OWNER.may_load(deps.storage)?;
+        let AcceptedDenomsResp { denoms } = accepted_denoms(deps)?;
+        let Cw20TokensResp { tokens } = cw20_tokens(deps)?;
+
+        Ok(ConfigResp {
+            owner,
+            minimal_donation: denoms,
+            cw20_tokens: tokens,
+            strict: config.strict,
+            refund: config.refund,
+            reward_collection: config.reward_collection,
+            milestones: config.milestones,
+            governance: config.governance,
+            approval_policy: config.approval_policy,
+            access_mode: config.access_mode,
+            rate_limit: config.rate_limit,
+            max_memo_length: config.max_memo_length,
+        })
+    }
+
+    pub fn pending_withdrawal(deps: Deps, env: Env, id: u64) -> StdResult<PendingWithdrawalResp> {
+        let pending = PENDING_WITHDRAWALS.load(deps.storage, id)?;
+        Ok(pending_withdrawal_resp(id, pending, &env.block))
+    }
+
+    pub fn pending_withdrawals(
+        deps: Deps,
+        env: Env,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<PendingWithdrawalsResp> {
+        let start = start_after.map(Bound::exclusive);
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        let withdrawals = PENDING_WITHDRAWALS
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(id, pending)| pending_withdrawal_resp(id, pending, &env.block)))
+            .collect::<StdResult<_>>()?;
+
+        Ok(PendingWithdrawalsResp { withdrawals })
+    }
+
+    fn pending_withdrawal_resp(
+        id: u64,
+        pending: PendingWithdrawal,
+        block: &BlockInfo,
+    ) -> PendingWithdrawalResp {
+        PendingWithdrawalResp {
+            id,
+            status: approvals::status(&pending, block),
+            requester: pending.requester,
+            target: pending.target,
+            amount: pending.amount,
+            expires: pending.expires,
+            approvals: pending.approvals,
+        }
+    }
+
+    pub fn proposal(deps: Deps, env: Env, id: u64) -> StdResult<ProposalResp> {
+        let proposal = PROPOSALS.load(deps.storage, id)?;
+        Ok(proposal_resp(id, proposal, &env.block))
+    }
+
+    pub fn proposals(
+        deps: Deps,
+        env: Env,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ProposalsResp> {
+        let start = start_after.map(Bound::exclusive);
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        let proposals = PROPOSALS
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(id, proposal)| proposal_resp(id, proposal, &env.block)))
+            .collect::<StdResult<_>>()?;
+
+        Ok(ProposalsResp { proposals })
+    }
+
+    pub fn votes(
+        deps: Deps,
+        proposal_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<VotesResp> {
+        let start_after = start_after
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?;
+        let start = start_after.as_ref().map(Bound::exclusive);
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        let votes = VOTES
+            .prefix(proposal_id)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(voter, ballot)| VoteResp {
+                    voter,
+                    vote: ballot.vote,
+                    weight: ballot.weight,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(VotesResp { votes })
+    }
+
+    fn proposal_resp(id: u64, proposal: Proposal, block: &BlockInfo) -> ProposalResp {
+        ProposalResp {
+            id,
+            status: governance::status(&proposal, block),
+            proposer: proposal.proposer,
+            receiver: proposal.receiver,
+            amount: proposal.amount,
+            description: proposal.description,
+            expires: proposal.expires,
+            total_weight: proposal.total_weight,
+            yes: proposal.yes,
+            no: proposal.no,
+            abstain: proposal.abstain,
+        }
+    }
+
+    pub fn status(deps: Deps) -> StdResult<StatusResp> {
+        let mut paused = vec![];
+        for operation in Operation::ALL {
+            if let Some(pause) = PAUSED.may_load(deps.storage, operation.as_str())? {
+                paused.push(PausedOperation {
+                    operation,
+                    paused_by: pause.paused_by,
+                    since: pause.height,
+                });
+            }
+        }
+
+        Ok(StatusResp { paused })
+    }
+
+    pub fn cw20_tokens(deps: Deps) -> StdResult<Cw20TokensResp> {
+        let tokens = CW20_TOKENS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(address, amount)| Cw20CoinVerified { address, amount }))
+            .collect::<StdResult<_>>()?;
+
+        Ok(Cw20TokensResp { tokens })
+    }
+
+    pub fn accepted_denoms(deps: Deps) -> StdResult<AcceptedDenomsResp> {
+        let denoms = ACCEPTED_DENOMS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+            .collect::<StdResult<_>>()?;
+
+        Ok(AcceptedDenomsResp { denoms })
+    }
+
+    fn counter_resp(counter_id: String, state: CounterState) -> CounterResp {
+        CounterResp {
+            counter_id,
+            value: state.value,
+            minimal_donation: state.minimal_donation,
+            owner: state.owner,
+        }
+    }
+
+    fn donor_resp(address: Addr, record: DonorRecord) -> DonorResp {
+        DonorResp {
+            address,
+            total: record.total,
+            donations: record.donations,
+            first_donation_height: record.first_donation_height,
+            last_donation_height: record.last_donation_height,
+        }
+    }
+
     // pub fn incremented(value: u64) -> ValueResp {
     //     ValueResp { value: value + 1 }
     // }
 }
 
 pub mod exec {
-    use cosmwasm_std::{BankMsg, Coin, DepsMut, Env, MessageInfo, Response, Uint128};
+    use cosmwasm_std::{
+        from_json, to_json_binary, to_json_string, Addr, BankMsg, Coin, CosmosMsg, Deps, DepsMut,
+        Env, IbcMsg, IbcTimeout, MessageInfo, Order, ReplyOn, Response, StdResult, Storage, SubMsg,
+        Uint128, WasmMsg,
+    };
+    use cw20::{
+        BalanceResponse, Cw20Coin, Cw20CoinVerified, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg,
+    };
+    use cw_utils::Expiration;
 
+    use super::{add_coins, save_counter};
     use crate::{
+        access, approvals, beneficiaries, campaign,
         error::ContractError,
-        state::{COUNTER, MINIMAL_DONATION, OWNER},
+        events::{
+            join_coins, AddAcceptedDenomEvent, AddCw20TokenEvent, AddIbcChannelEvent,
+            AddToAccessListEvent, ApproveWithdrawalEvent, ConfigUpdateEvent, DistributeEvent,
+            DonateEvent, ExecuteProposalEvent, FeeUpdateEvent, GrantRoleEvent, HideMemoEvent,
+            PauseEvent, RemoveAcceptedDenomEvent, RemoveCw20TokenEvent, RemoveFromAccessListEvent,
+            RemoveIbcChannelEvent, ResetEvent, RevokeApprovalEvent, RevokeRoleEvent,
+            SetBeneficiariesEvent, SpendProposalEvent, UnpauseEvent, VoteEvent, WithdrawEvent,
+            WithdrawIbcEvent, WithdrawToEvent, WithdrawalRequestEvent,
+        },
+        fees, governance,
+        msg::{
+            AccessListKind, ApprovalPolicy, Beneficiary, CampaignPhase, ChangeCause, ConfigUpdate,
+            DonationCap, Operation, PendingWithdrawalStatus, ProposalStatus, ReceiveMsg,
+            RefundPolicy, Role, VoteOption, WithdrawalOutcome, WithdrawalTarget, DEFAULT_COUNTER,
+            IBC_DEFAULT_TIMEOUT,
+        },
+        ownership, pause, rate_limit, rewards,
+        state::{
+            donations, Ballot, Config, CounterState, Donation, DonorRecord, Pause, PendingOwner,
+            PendingWithdrawal, Proposal, WithdrawalRecord, ACCEPTED_DENOMS, BENEFICIARIES, CONFIG,
+            CONTRIBUTIONS, COUNTERS, CW20_TOKENS, DONORS, FEE, IBC_CHANNELS, OWNER, PAUSED,
+            PENDING_OWNER, PENDING_WITHDRAWALS, PROPOSALS, ROLES, VOTES, WITHDRAWALS,
+        },
+        stats,
     };
 
+    pub(crate) const CW20_DENOM_PREFIX: &str = "cw20:";
+
     // pub fn poke(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
     //     let counter = COUNTER.load(deps.storage)? + 1;
     //     COUNTER.save(deps.storage, &counter)?;
@@ -47,66 +795,366 @@ pub mod exec {
 
     pub fn reset(
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
+        counter_id: Option<String>,
         counter: u64,
     ) -> Result<Response, ContractError> {
-        let owner = OWNER.load(deps.storage)?;
-        if info.sender != owner {
-            return Err(ContractError::Unauthorized {
-                owner: owner.to_string(),
-            });
+        pause::ensure_not_paused(deps.storage, Operation::Resets)?;
+        let counter_id = counter_id.as_deref().unwrap_or(DEFAULT_COUNTER);
+        let mut state = load_counter(deps.storage, counter_id)?;
+
+        if state.owner.as_ref() != Some(&info.sender) {
+            ownership::ensure_role(deps.storage, Role::Resetter, &info.sender)?;
         }
 
-        COUNTER.save(deps.storage, &counter)?;
+        let counter_before = state.value;
+        state.value = counter;
+        save_counter(
+            deps.storage,
+            env.block.height,
+            counter_id,
+            &state,
+            ChangeCause::Reset,
+            Some(&info.sender),
+        )?;
 
-        let resp = Response::new()
-            .add_attribute("action", "reset")
-            .add_attribute("sender", info.sender.as_str())
-            .add_attribute("counter", counter.to_string());
+        let resp = Response::new().add_event(
+            ResetEvent {
+                sender: info.sender,
+                counter_id: counter_id.to_owned(),
+                counter_before,
+                counter_after: counter,
+            }
+            .into(),
+        );
 
         Ok(resp)
     }
 
-    pub fn donate(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
-        let counter = COUNTER.load(deps.storage)?;
-        let minimal_donation = MINIMAL_DONATION.load(deps.storage)?;
+    pub fn create_counter(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        counter_id: String,
+        minimal_donation: Coin,
+        owner: Option<String>,
+    ) -> Result<Response, ContractError> {
+        ownership::ensure_role(deps.storage, Role::Admin, &info.sender)?;
 
-        if minimal_donation.amount.is_zero()
-            || info.funds.iter().any(|coin| {
-                coin.denom == minimal_donation.denom && coin.amount >= minimal_donation.amount
-            })
-        {
-            let mut counter = COUNTER.load(deps.storage)?;
-            counter += 1;
-            COUNTER.save(deps.storage, &counter)?;
+        if counter_id.is_empty() {
+            return Err(ContractError::EmptyCounterId {});
+        }
+        if COUNTERS.may_load(deps.storage, &counter_id)?.is_some() {
+            return Err(ContractError::CounterExists { counter_id });
         }
 
+        let owner = match owner {
+            Some(owner) => deps.api.addr_validate(&owner)?,
+            None => info.sender.clone(),
+        };
+
+        save_counter(
+            deps.storage,
+            env.block.height,
+            &counter_id,
+            &CounterState {
+                value: 0,
+                minimal_donation: Some(minimal_donation.clone()),
+                owner: Some(owner.clone()),
+            },
+            ChangeCause::Create,
+            Some(&info.sender),
+        )?;
+
         let resp = Response::new()
-            .add_attribute("action", "donate")
+            .add_attribute("action", "create_counter")
             .add_attribute("sender", info.sender.as_str())
-            .add_attribute("minimal_donation", minimal_donation.to_string())
-            .add_attribute("counter", counter.to_string());
+            .add_attribute("counter_id", counter_id)
+            .add_attribute("owner", owner.as_str())
+            .add_attribute("minimal_donation", minimal_donation.to_string());
+
         Ok(resp)
     }
 
-    pub fn withdraw(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
-        let owner = OWNER.load(deps.storage)?;
-        if info.sender != owner {
-            return Err(ContractError::Unauthorized {
-                owner: owner.to_string(),
+    pub fn donate(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        counter_id: Option<String>,
+        memo: Option<String>,
+    ) -> Result<Response, ContractError> {
+        pause::ensure_not_paused(deps.storage, Operation::Donations)?;
+        campaign::ensure_open(deps.storage, &env.block)?;
+
+        let config = CONFIG.load(deps.storage)?;
+        access::ensure_allowed(deps.storage, config.access_mode, &info.sender)?;
+        ensure_memo_length(&config, memo.as_deref())?;
+        let counter_id = counter_id.as_deref().unwrap_or(DEFAULT_COUNTER);
+        let mut state = load_counter(deps.storage, counter_id)?;
+        let counter = state.value;
+        let accepted = match &state.minimal_donation {
+            Some(coin) => vec![(coin.denom.clone(), coin.amount)],
+            None => ACCEPTED_DENOMS
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?,
+        };
+
+        let counted = accepted.iter().any(|(denom, minimal)| {
+            minimal.is_zero()
+                || info
+                    .funds
+                    .iter()
+                    .any(|coin| coin.denom == *denom && coin.amount >= *minimal)
+        });
+
+        if config.strict && !counted {
+            return Err(rejection(&accepted, &info.funds));
+        }
+        let counted = counted
+            && rate_limit::record(
+                deps.storage,
+                config.rate_limit.as_ref(),
+                &env.block,
+                &info.sender,
+            )?;
+
+        let mut reward = None;
+        if counted {
+            state.value += 1;
+            save_counter(
+                deps.storage,
+                env.block.height,
+                counter_id,
+                &state,
+                ChangeCause::Donate,
+                Some(&info.sender),
+            )?;
+            reward = rewards::reward_msg(
+                deps.storage,
+                &config,
+                &env,
+                &info.sender,
+                counter_id,
+                state.value,
+            )?;
+        }
+
+        let (kept, refunded) = split_refund(&config.refund, &accepted, &info.funds);
+        let skim = if counted {
+            // Kept coins of denoms which are not accepted are not charged
+            let kept_accepted: Vec<_> = kept
+                .iter()
+                .filter(|coin| accepted.iter().any(|(denom, _)| *denom == coin.denom))
+                .cloned()
+                .collect();
+            fees::skim(deps.storage, &kept_accepted)?
+        } else {
+            None
+        };
+
+        if counted || !kept.is_empty() {
+            record_donation(
+                deps.storage,
+                &env,
+                &info.sender,
+                counter_id,
+                &kept,
+                counted,
+                memo,
+            )?;
+        } else if memo.is_some() {
+            return Err(ContractError::MemoNotLogged {});
+        }
+        campaign::record(deps.storage, &info.sender, &fees::net(&kept, skim.as_ref()))?;
+
+        let mut resp = Response::new().add_submessages(reward);
+        if !refunded.is_empty() {
+            resp = resp.add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: refunded.clone(),
+            });
+        }
+        if let Some(skim) = &skim {
+            resp = resp.add_message(BankMsg::Send {
+                to_address: skim.collector.to_string(),
+                amount: skim.fee.clone(),
             });
         }
 
-        let balance = deps.querier.query_all_balances(&env.contract.address)?;
-        let bank_msg = BankMsg::Send {
-            to_address: info.sender.to_string(),
-            amount: balance,
+        let resp = resp.add_event(
+            DonateEvent {
+                sender: info.sender,
+                counter_id: counter_id.to_owned(),
+                kept,
+                refunded,
+                fee: skim.map(|skim| skim.fee).unwrap_or_default(),
+                counter_before: counter,
+                counter_after: state.value,
+            }
+            .into(),
+        );
+        Ok(resp)
+    }
+
+    pub fn receive(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: Cw20ReceiveMsg,
+    ) -> Result<Response, ContractError> {
+        let donor = deps.api.addr_validate(&msg.sender)?;
+
+        match from_json(&msg.msg)? {
+            ReceiveMsg::Donate { counter_id, memo } => {
+                donate_cw20(deps, env, info.sender, donor, msg.amount, counter_id, memo)
+            }
+        }
+    }
+
+    fn donate_cw20(
+        deps: DepsMut,
+        env: Env,
+        token: Addr,
+        donor: Addr,
+        amount: Uint128,
+        counter_id: Option<String>,
+        memo: Option<String>,
+    ) -> Result<Response, ContractError> {
+        pause::ensure_not_paused(deps.storage, Operation::Donations)?;
+        campaign::ensure_open(deps.storage, &env.block)?;
+        let config = CONFIG.load(deps.storage)?;
+        access::ensure_allowed(deps.storage, config.access_mode, &donor)?;
+        ensure_memo_length(&config, memo.as_deref())?;
+
+        let unsupported = || ContractError::UnsupportedToken {
+            token: token.to_string(),
         };
+        let minimal_donation = CW20_TOKENS
+            .may_load(deps.storage, &token)?
+            .ok_or_else(unsupported)?;
 
-        let resp = Response::new()
-            .add_message(bank_msg)
-            .add_attribute("action", "withdraw")
-            .add_attribute("sender", info.sender.as_str());
+        let counter_id = counter_id.as_deref().unwrap_or(DEFAULT_COUNTER);
+        let mut state = load_counter(deps.storage, counter_id)?;
+        let minimal_donation = match &state.minimal_donation {
+            Some(coin) if coin.denom == cw20_denom(&token) => coin.amount,
+            Some(_) => return Err(unsupported()),
+            None => minimal_donation,
+        };
+
+        let counter = state.value;
+        let counted = amount >= minimal_donation;
+
+        if config.strict && !counted {
+            return Err(ContractError::InsufficientDonation {
+                required: Coin {
+                    denom: cw20_denom(&token),
+                    amount: minimal_donation,
+                },
+                sent: Coin {
+                    denom: cw20_denom(&token),
+                    amount,
+                },
+            });
+        }
+        let counted = counted
+            && rate_limit::record(deps.storage, config.rate_limit.as_ref(), &env.block, &donor)?;
+
+        let mut reward = None;
+        if counted {
+            state.value += 1;
+            save_counter(
+                deps.storage,
+                env.block.height,
+                counter_id,
+                &state,
+                ChangeCause::Donate,
+                Some(&donor),
+            )?;
+            reward =
+                rewards::reward_msg(deps.storage, &config, &env, &donor, counter_id, state.value)?;
+        }
+
+        let denom = cw20_denom(&token);
+        let (kept, refunded) = split_refund(
+            &config.refund,
+            &[(denom.clone(), minimal_donation)],
+            &[Coin { denom, amount }],
+        );
+        let skim = if counted {
+            fees::skim(deps.storage, &kept)?
+        } else {
+            None
+        };
+        record_donation(deps.storage, &env, &donor, counter_id, &kept, counted, memo)?;
+        campaign::record(deps.storage, &donor, &fees::net(&kept, skim.as_ref()))?;
+
+        let mut resp = Response::new().add_submessages(reward);
+        if let Some(refund) = refunded.first() {
+            resp = resp.add_message(WasmMsg::Execute {
+                contract_addr: token.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: donor.to_string(),
+                    amount: refund.amount,
+                })?,
+                funds: vec![],
+            });
+        }
+        if let Some(skim) = &skim {
+            for fee in &skim.fee {
+                resp = resp.add_message(WasmMsg::Execute {
+                    contract_addr: token.to_string(),
+                    msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: skim.collector.to_string(),
+                        amount: fee.amount,
+                    })?,
+                    funds: vec![],
+                });
+            }
+        }
+
+        let resp = resp.add_event(
+            DonateEvent {
+                sender: donor,
+                counter_id: counter_id.to_owned(),
+                kept,
+                refunded,
+                fee: skim.map(|skim| skim.fee).unwrap_or_default(),
+                counter_before: counter,
+                counter_after: state.value,
+            }
+            .into(),
+        );
+        Ok(resp)
+    }
+
+    pub fn withdraw(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+        pause::ensure_not_paused(deps.storage, Operation::Withdrawals)?;
+        ownership::ensure_role(deps.storage, Role::Treasurer, &info.sender)?;
+        governance::ensure_disabled(deps.storage)?;
+        campaign::ensure_withdrawable(deps.storage, &env.block)?;
+
+        let balance = deps.querier.query_all_balances(&env.contract.address)?;
+        let cw20_balance = cw20_balances(deps.as_ref(), &env)?;
+
+        let payouts = payouts(info.sender.as_str(), balance, cw20_balance)?;
+        let amount = payout_total(&payouts);
+        if let Some(policy) = approvals::exceeded_policy(deps.storage, &env.block, &amount)? {
+            let target = WithdrawalTarget::Address {
+                receiver: info.sender.clone(),
+            };
+            return request_withdrawal(deps.storage, &env, &policy, info.sender, target, amount);
+        }
+        let msgs = withdrawal_msgs(deps.storage, &env, &info.sender, payouts, ReplyOn::Always)?;
+
+        let resp = Response::new().add_submessages(msgs).add_event(
+            WithdrawEvent {
+                sender: info.sender.clone(),
+                receiver: info.sender,
+                amount,
+            }
+            .into(),
+        );
 
         Ok(resp)
     }
@@ -117,38 +1165,1607 @@ pub mod exec {
         info: MessageInfo,
         receiver: String,
         funds: Vec<Coin>,
+        cw20_funds: Vec<Cw20Coin>,
     ) -> Result<Response, ContractError> {
-        let owner = OWNER.load(deps.storage)?;
-        if info.sender != owner {
-            return Err(ContractError::Unauthorized {
-                owner: owner.to_string(),
-            });
-        }
+        pause::ensure_not_paused(deps.storage, Operation::Withdrawals)?;
+        ownership::ensure_role(deps.storage, Role::Treasurer, &info.sender)?;
+        governance::ensure_disabled(deps.storage)?;
+        campaign::ensure_withdrawable(deps.storage, &env.block)?;
 
+        let receiver = deps.api.addr_validate(&receiver)?;
         let mut balance = deps.querier.query_all_balances(&env.contract.address)?;
 
-        if !funds.is_empty() {
-            for coin in &mut balance {
-                let limit = funds
-                    .iter()
-                    .find(|c| c.denom == coin.denom)
-                    .map(|c| c.amount)
-                    .unwrap_or(Uint128::zero());
+        if funds.is_empty() && cw20_funds.is_empty() {
+            let cw20_balance = cw20_balances(deps.as_ref(), &env)?;
+            return send_to(deps, env, info.sender, receiver, balance, cw20_balance);
+        }
 
-                coin.amount = std::cmp::min(coin.amount, limit);
-            }
+        for coin in &mut balance {
+            let limit = funds
+                .iter()
+                .find(|c| c.denom == coin.denom)
+                .map(|c| c.amount)
+                .unwrap_or(Uint128::zero());
+
+            coin.amount = std::cmp::min(coin.amount, limit);
         }
 
-        let bank_msg = BankMsg::Send {
-            to_address: receiver,
-            amount: balance,
-        };
+        let cw20_balance = cw20_funds
+            .into_iter()
+            .map(|limit| {
+                let token = deps.api.addr_validate(&limit.address)?;
+                let amount = cw20_balance(deps.as_ref(), &env, &token)?;
+                Ok(Cw20CoinVerified {
+                    address: token,
+                    amount: std::cmp::min(amount, limit.amount),
+                })
+            })
+            .collect::<StdResult<_>>()?;
 
-        let resp = Response::new()
-            .add_message(bank_msg)
-            .add_attribute("action", "withdraw")
-            .add_attribute("sender", info.sender.as_str());
+        send_to(deps, env, info.sender, receiver, balance, cw20_balance)
+    }
 
-        Ok(resp)
+    /// Pays out `balance` and `cw20_balance` to `receiver`, or creates a pending withdrawal if
+    /// they exceed the limits of the approval policy
+    fn send_to(
+        deps: DepsMut,
+        env: Env,
+        sender: Addr,
+        receiver: Addr,
+        balance: Vec<Coin>,
+        cw20_balance: Vec<Cw20CoinVerified>,
+    ) -> Result<Response, ContractError> {
+        let payouts = payouts(receiver.as_str(), balance, cw20_balance)?;
+        let amount = payout_total(&payouts);
+        if let Some(policy) = approvals::exceeded_policy(deps.storage, &env.block, &amount)? {
+            let target = WithdrawalTarget::Address { receiver };
+            return request_withdrawal(deps.storage, &env, &policy, sender, target, amount);
+        }
+        let msgs = withdrawal_msgs(deps.storage, &env, &receiver, payouts, ReplyOn::Always)?;
+
+        let resp = Response::new().add_submessages(msgs).add_event(
+            WithdrawToEvent {
+                sender,
+                receiver,
+                amount,
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    pub fn withdraw_ibc(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        channel_id: String,
+        remote_address: String,
+        funds: Vec<Coin>,
+        timeout: Option<u64>,
+    ) -> Result<Response, ContractError> {
+        pause::ensure_not_paused(deps.storage, Operation::Withdrawals)?;
+        ownership::ensure_role(deps.storage, Role::Treasurer, &info.sender)?;
+        governance::ensure_disabled(deps.storage)?;
+        campaign::ensure_withdrawable(deps.storage, &env.block)?;
+
+        if !IBC_CHANNELS.has(deps.storage, &channel_id) {
+            return Err(ContractError::ChannelNotAllowed { channel_id });
+        }
+
+        let timeout = timeout.unwrap_or(IBC_DEFAULT_TIMEOUT);
+
+        let mut amount = vec![];
+        for coin in funds {
+            let balance = deps
+                .querier
+                .query_balance(&env.contract.address, &coin.denom)?;
+            let coin = Coin {
+                amount: std::cmp::min(coin.amount, balance.amount),
+                denom: coin.denom,
+            };
+            if !coin.amount.is_zero() {
+                add_coins(&mut amount, &[coin]);
+            }
+        }
+
+        if amount.is_empty() {
+            return Err(ContractError::NoFunds {});
+        }
+        if let Some(policy) = approvals::exceeded_policy(deps.storage, &env.block, &amount)? {
+            let target = WithdrawalTarget::Ibc {
+                channel_id,
+                remote_address,
+                timeout,
+            };
+            return request_withdrawal(deps.storage, &env, &policy, info.sender, target, amount);
+        }
+        stats::record_withdrawal(deps.storage, env.block.height, &amount)?;
+
+        let msgs = ibc_transfer_msgs(&env, &channel_id, &remote_address, &amount, timeout);
+
+        let resp = Response::new().add_messages(msgs).add_event(
+            WithdrawIbcEvent {
+                sender: info.sender,
+                channel_id,
+                remote_address,
+                amount,
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    pub fn add_ibc_channel(
+        deps: DepsMut,
+        info: MessageInfo,
+        channel_id: String,
+    ) -> Result<Response, ContractError> {
+        ownership::ensure_role(deps.storage, Role::Admin, &info.sender)?;
+
+        IBC_CHANNELS.save(deps.storage, &channel_id, &())?;
+
+        let resp = Response::new().add_event(
+            AddIbcChannelEvent {
+                sender: info.sender,
+                channel_id,
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    pub fn remove_ibc_channel(
+        deps: DepsMut,
+        info: MessageInfo,
+        channel_id: String,
+    ) -> Result<Response, ContractError> {
+        ownership::ensure_role(deps.storage, Role::Admin, &info.sender)?;
+
+        if !IBC_CHANNELS.has(deps.storage, &channel_id) {
+            return Err(ContractError::ChannelNotAllowed { channel_id });
+        }
+        IBC_CHANNELS.remove(deps.storage, &channel_id);
+
+        let resp = Response::new().add_event(
+            RemoveIbcChannelEvent {
+                sender: info.sender,
+                channel_id,
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    pub fn hide_memo(deps: DepsMut, info: MessageInfo, id: u64) -> Result<Response, ContractError> {
+        ownership::ensure_role(deps.storage, Role::Admin, &info.sender)?;
+
+        let donations = donations();
+        let mut donation = donations
+            .may_load(deps.storage, id)?
+            .ok_or(ContractError::DonationNotFound { id })?;
+        donation.memo = None;
+        donation.memo_hidden = true;
+        donations.save(deps.storage, id, &donation)?;
+
+        let resp = Response::new().add_event(
+            HideMemoEvent {
+                sender: info.sender,
+                id,
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    pub fn add_to_access_list(
+        deps: DepsMut,
+        info: MessageInfo,
+        list: AccessListKind,
+        addresses: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        ownership::ensure_role(deps.storage, Role::Admin, &info.sender)?;
+
+        let addresses = addresses
+            .iter()
+            .map(|address| deps.api.addr_validate(address))
+            .collect::<StdResult<Vec<_>>>()?;
+        for address in &addresses {
+            access::list(list).save(deps.storage, address, &())?;
+        }
+
+        let resp = Response::new().add_event(
+            AddToAccessListEvent {
+                sender: info.sender,
+                list,
+                addresses,
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    pub fn remove_from_access_list(
+        deps: DepsMut,
+        info: MessageInfo,
+        list: AccessListKind,
+        addresses: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        ownership::ensure_role(deps.storage, Role::Admin, &info.sender)?;
+
+        let addresses = addresses
+            .iter()
+            .map(|address| deps.api.addr_validate(address))
+            .collect::<StdResult<Vec<_>>>()?;
+        for address in &addresses {
+            access::list(list).remove(deps.storage, address);
+        }
+
+        let resp = Response::new().add_event(
+            RemoveFromAccessListEvent {
+                sender: info.sender,
+                list,
+                addresses,
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    pub fn pause(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        operation: Operation,
+    ) -> Result<Response, ContractError> {
+        ownership::ensure_role(deps.storage, Role::Guardian, &info.sender)?;
+
+        if PAUSED.has(deps.storage, operation.as_str()) {
+            return Err(ContractError::AlreadyPaused { operation });
+        }
+        PAUSED.save(
+            deps.storage,
+            operation.as_str(),
+            &Pause {
+                paused_by: info.sender.clone(),
+                height: env.block.height,
+            },
+        )?;
+
+        let resp = Response::new().add_event(
+            PauseEvent {
+                sender: info.sender,
+                operation,
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    pub fn unpause(
+        deps: DepsMut,
+        info: MessageInfo,
+        operation: Operation,
+    ) -> Result<Response, ContractError> {
+        ownership::ensure_role(deps.storage, Role::Guardian, &info.sender)?;
+
+        if !PAUSED.has(deps.storage, operation.as_str()) {
+            return Err(ContractError::NotPaused { operation });
+        }
+        PAUSED.remove(deps.storage, operation.as_str());
+
+        let resp = Response::new().add_event(
+            UnpauseEvent {
+                sender: info.sender,
+                operation,
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    pub fn approve_withdrawal(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        id: u64,
+    ) -> Result<Response, ContractError> {
+        let policy = CONFIG
+            .load(deps.storage)?
+            .approval_policy
+            .ok_or(ContractError::NotApprover {})?;
+        approvals::ensure_approver(&policy, &info.sender)?;
+
+        let mut pending = load_open_withdrawal(deps.storage, &env, id)?;
+        if pending.approvals.contains(&info.sender) {
+            return Err(ContractError::AlreadyApproved { id });
+        }
+        pending.approvals.push(info.sender.clone());
+        PENDING_WITHDRAWALS.save(deps.storage, id, &pending)?;
+
+        let resp = Response::new().add_event(
+            ApproveWithdrawalEvent {
+                sender: info.sender,
+                id,
+                approvals: approvals::valid_approvals(&policy, &pending),
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    pub fn revoke_approval(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        id: u64,
+    ) -> Result<Response, ContractError> {
+        let mut pending = load_open_withdrawal(deps.storage, &env, id)?;
+        let position = pending
+            .approvals
+            .iter()
+            .position(|approver| *approver == info.sender)
+            .ok_or(ContractError::NotApproved { id })?;
+        pending.approvals.remove(position);
+        PENDING_WITHDRAWALS.save(deps.storage, id, &pending)?;
+
+        let resp = Response::new().add_event(
+            RevokeApprovalEvent {
+                sender: info.sender,
+                id,
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    pub fn execute_withdrawal(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        id: u64,
+    ) -> Result<Response, ContractError> {
+        pause::ensure_not_paused(deps.storage, Operation::Withdrawals)?;
+        governance::ensure_disabled(deps.storage)?;
+        campaign::ensure_withdrawable(deps.storage, &env.block)?;
+
+        let mut pending = load_open_withdrawal(deps.storage, &env, id)?;
+        // Requests of former treasurers can't be executed anymore
+        ownership::ensure_role(deps.storage, Role::Treasurer, &pending.requester)?;
+        // Without a policy withdrawals don't need approvals anymore
+        if let Some(policy) = CONFIG.load(deps.storage)?.approval_policy {
+            let approvals = approvals::valid_approvals(&policy, &pending);
+            if approvals < policy.threshold {
+                return Err(ContractError::InsufficientApprovals {
+                    id,
+                    approvals,
+                    threshold: policy.threshold,
+                });
+            }
+        }
+
+        if let WithdrawalTarget::Ibc { channel_id, .. } = &pending.target {
+            if !IBC_CHANNELS.has(deps.storage, channel_id) {
+                return Err(ContractError::ChannelNotAllowed {
+                    channel_id: channel_id.clone(),
+                });
+            }
+        }
+        ensure_balance(deps.as_ref(), &env, &pending.amount)?;
+        // Payouts are sent without catching their errors, a failed one reverts this and the
+        // withdrawal can be executed again
+        pending.executed = true;
+        PENDING_WITHDRAWALS.save(deps.storage, id, &pending)?;
+
+        let resp = match pending.target {
+            WithdrawalTarget::Address { receiver } => {
+                let (native, cw20) = split_cw20(pending.amount.clone());
+                let payouts = payouts(receiver.as_str(), native, cw20)?;
+                let msgs =
+                    withdrawal_msgs(deps.storage, &env, &receiver, payouts, ReplyOn::Success)?;
+
+                Response::new().add_submessages(msgs).add_event(
+                    WithdrawToEvent {
+                        sender: info.sender,
+                        receiver,
+                        amount: pending.amount,
+                    }
+                    .into(),
+                )
+            }
+            WithdrawalTarget::Ibc {
+                channel_id,
+                remote_address,
+                timeout,
+            } => {
+                stats::record_withdrawal(deps.storage, env.block.height, &pending.amount)?;
+                let msgs =
+                    ibc_transfer_msgs(&env, &channel_id, &remote_address, &pending.amount, timeout);
+
+                Response::new().add_messages(msgs).add_event(
+                    WithdrawIbcEvent {
+                        sender: info.sender,
+                        channel_id,
+                        remote_address,
+                        amount: pending.amount,
+                    }
+                    .into(),
+                )
+            }
+            WithdrawalTarget::Beneficiaries {} => {
+                let (msgs, amount) =
+                    distribution_msgs(deps.storage, &env, pending.amount, ReplyOn::Success)?;
+
+                Response::new().add_submessages(msgs).add_event(
+                    DistributeEvent {
+                        sender: info.sender,
+                        amount,
+                    }
+                    .into(),
+                )
+            }
+        };
+
+        Ok(resp)
+    }
+
+    pub fn spend_proposal(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        receiver: String,
+        amount: Vec<Coin>,
+        description: String,
+    ) -> Result<Response, ContractError> {
+        ownership::ensure_role(deps.storage, Role::Treasurer, &info.sender)?;
+        let governance = governance::load(deps.storage)?;
+
+        let receiver = deps.api.addr_validate(&receiver)?;
+        let amount: Vec<_> = amount
+            .into_iter()
+            .filter(|coin| !coin.amount.is_zero())
+            .collect();
+        if amount.is_empty() {
+            return Err(ContractError::NoFunds {});
+        }
+
+        let id = PROPOSALS
+            .keys(deps.storage, None, None, Order::Descending)
+            .next()
+            .transpose()?
+            .map_or(0, |last| last + 1);
+        let total_weight =
+            governance::total_weight(deps.storage, &governance.denom, env.block.height)?;
+
+        PROPOSALS.save(
+            deps.storage,
+            id,
+            &Proposal {
+                proposer: info.sender.clone(),
+                receiver: receiver.clone(),
+                amount: amount.clone(),
+                description,
+                start_height: env.block.height,
+                expires: governance.voting_period.after(&env.block),
+                governance,
+                total_weight,
+                yes: Uint128::zero(),
+                no: Uint128::zero(),
+                abstain: Uint128::zero(),
+                executed: false,
+            },
+        )?;
+
+        let resp = Response::new().add_event(
+            SpendProposalEvent {
+                sender: info.sender,
+                proposal_id: id,
+                receiver,
+                amount,
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    pub fn vote(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        proposal_id: u64,
+        vote: VoteOption,
+    ) -> Result<Response, ContractError> {
+        let mut proposal = load_proposal(deps.storage, proposal_id)?;
+        if governance::status(&proposal, &env.block) != ProposalStatus::Open {
+            return Err(ContractError::VotingClosed { id: proposal_id });
+        }
+        if VOTES.has(deps.storage, (proposal_id, &info.sender)) {
+            return Err(ContractError::AlreadyVoted { id: proposal_id });
+        }
+
+        let weight = governance::weight(deps.storage, &proposal, &info.sender)?;
+        if weight.is_zero() {
+            return Err(ContractError::NoVotingWeight {
+                denom: proposal.governance.denom,
+            });
+        }
+
+        match vote {
+            VoteOption::Yes => proposal.yes += weight,
+            VoteOption::No => proposal.no += weight,
+            VoteOption::Abstain => proposal.abstain += weight,
+        }
+        PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+        VOTES.save(
+            deps.storage,
+            (proposal_id, &info.sender),
+            &Ballot { vote, weight },
+        )?;
+
+        let resp = Response::new().add_event(
+            VoteEvent {
+                sender: info.sender,
+                proposal_id,
+                vote,
+                weight,
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    pub fn execute_proposal(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        proposal_id: u64,
+    ) -> Result<Response, ContractError> {
+        pause::ensure_not_paused(deps.storage, Operation::Withdrawals)?;
+        campaign::ensure_withdrawable(deps.storage, &env.block)?;
+
+        let mut proposal = load_proposal(deps.storage, proposal_id)?;
+        if governance::status(&proposal, &env.block) != ProposalStatus::Passed {
+            return Err(ContractError::ProposalNotPassed { id: proposal_id });
+        }
+
+        // Payouts failing in their submessages would still mark the proposal as executed
+        ensure_balance(deps.as_ref(), &env, &proposal.amount)?;
+
+        proposal.executed = true;
+        PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+
+        let (native, cw20) = split_cw20(proposal.amount.clone());
+        let payouts = payouts(proposal.receiver.as_str(), native, cw20)?;
+        let msgs = withdrawal_msgs(
+            deps.storage,
+            &env,
+            &proposal.receiver,
+            payouts,
+            ReplyOn::Always,
+        )?;
+
+        let resp = Response::new().add_submessages(msgs).add_event(
+            ExecuteProposalEvent {
+                sender: info.sender,
+                proposal_id,
+                receiver: proposal.receiver,
+                amount: proposal.amount,
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    pub fn set_beneficiaries(
+        deps: DepsMut,
+        info: MessageInfo,
+        list: Vec<Beneficiary>,
+        remainder: String,
+    ) -> Result<Response, ContractError> {
+        ownership::ensure_role(deps.storage, Role::Admin, &info.sender)?;
+
+        let validated = beneficiaries::validate(deps.api, list, &remainder)?;
+        BENEFICIARIES.save(deps.storage, &validated)?;
+
+        let resp = Response::new().add_event(
+            SetBeneficiariesEvent {
+                sender: info.sender,
+                beneficiaries: validated.shares,
+                remainder: validated.remainder,
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    pub fn distribute(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        pause::ensure_not_paused(deps.storage, Operation::Withdrawals)?;
+        ownership::ensure_role(deps.storage, Role::Treasurer, &info.sender)?;
+        governance::ensure_disabled(deps.storage)?;
+        campaign::ensure_withdrawable(deps.storage, &env.block)?;
+
+        if !BENEFICIARIES.exists(deps.storage) {
+            return Err(ContractError::NoBeneficiaries {});
+        }
+
+        let mut balance = deps.querier.query_all_balances(&env.contract.address)?;
+        balance.extend(
+            cw20_balances(deps.as_ref(), &env)?
+                .into_iter()
+                .map(|coin| Coin {
+                    denom: cw20_denom(&coin.address),
+                    amount: coin.amount,
+                }),
+        );
+        if let Some(policy) = approvals::exceeded_policy(deps.storage, &env.block, &balance)? {
+            let target = WithdrawalTarget::Beneficiaries {};
+            return request_withdrawal(deps.storage, &env, &policy, info.sender, target, balance);
+        }
+        let (msgs, amount) = distribution_msgs(deps.storage, &env, balance, ReplyOn::Always)?;
+
+        let resp = Response::new().add_submessages(msgs).add_event(
+            DistributeEvent {
+                sender: info.sender,
+                amount,
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    pub fn claim_refund(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        pause::ensure_not_paused(deps.storage, Operation::Withdrawals)?;
+        match campaign::phase(deps.storage, &env.block)? {
+            Some(CampaignPhase::Failed) => (),
+            Some(CampaignPhase::Succeeded) => return Err(ContractError::CampaignGoalReached {}),
+            Some(CampaignPhase::NotStarted | CampaignPhase::Open) => {
+                return Err(ContractError::CampaignNotFinished {})
+            }
+            None => return Err(ContractError::NothingToRefund {}),
+        }
+
+        let contribution = CONTRIBUTIONS
+            .may_load(deps.storage, &info.sender)?
+            .ok_or(ContractError::NothingToRefund {})?;
+        CONTRIBUTIONS.remove(deps.storage, &info.sender);
+
+        let (native, cw20) = split_cw20(contribution.clone());
+
+        let msgs = payouts(info.sender.as_str(), native, cw20)?
+            .into_iter()
+            .map(|(msg, _)| msg);
+
+        let resp = Response::new()
+            .add_messages(msgs)
+            .add_attribute("action", "claim_refund")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("refunded", join_coins(contribution));
+
+        Ok(resp)
+    }
+
+    pub fn propose_owner(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        new_owner: String,
+        expiry: Option<Expiration>,
+    ) -> Result<Response, ContractError> {
+        ownership::ensure_owner(deps.storage, &info.sender)?;
+
+        let new_owner = deps.api.addr_validate(&new_owner)?;
+        let expiry = expiry.unwrap_or_default();
+        if expiry.is_expired(&env.block) {
+            return Err(ContractError::TransferExpired {});
+        }
+
+        PENDING_OWNER.save(
+            deps.storage,
+            &PendingOwner {
+                owner: new_owner.clone(),
+                expiry,
+            },
+        )?;
+
+        let resp = Response::new()
+            .add_attribute("action", "propose_owner")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("pending_owner", new_owner.as_str())
+            .add_attribute("expiry", expiry.to_string());
+
+        Ok(resp)
+    }
+
+    pub fn accept_ownership(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let pending = ownership::load_pending(deps.storage, &env.block)?;
+        if info.sender != pending.owner {
+            return Err(ContractError::NotPendingOwner {
+                pending: pending.owner.to_string(),
+            });
+        }
+
+        OWNER.save(deps.storage, &pending.owner)?;
+        PENDING_OWNER.remove(deps.storage);
+
+        let resp = Response::new()
+            .add_attribute("action", "accept_ownership")
+            .add_attribute("sender", info.sender.as_str());
+
+        Ok(resp)
+    }
+
+    pub fn cancel_ownership_transfer(
+        deps: DepsMut,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        ownership::ensure_owner(deps.storage, &info.sender)?;
+
+        if !PENDING_OWNER.exists(deps.storage) {
+            return Err(ContractError::NoPendingOwner {});
+        }
+        PENDING_OWNER.remove(deps.storage);
+
+        let resp = Response::new()
+            .add_attribute("action", "cancel_ownership_transfer")
+            .add_attribute("sender", info.sender.as_str());
+
+        Ok(resp)
+    }
+
+    pub fn renounce_ownership(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+        ownership::ensure_owner(deps.storage, &info.sender)?;
+
+        OWNER.remove(deps.storage);
+        PENDING_OWNER.remove(deps.storage);
+
+        let resp = Response::new()
+            .add_attribute("action", "renounce_ownership")
+            .add_attribute("sender", info.sender.as_str());
+
+        Ok(resp)
+    }
+
+    pub fn grant_role(
+        deps: DepsMut,
+        info: MessageInfo,
+        role: Role,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        ownership::ensure_role(deps.storage, Role::Admin, &info.sender)?;
+
+        let address = deps.api.addr_validate(&address)?;
+        ROLES.save(deps.storage, (role.as_str(), &address), &())?;
+
+        let resp = Response::new().add_event(
+            GrantRoleEvent {
+                sender: info.sender,
+                role,
+                address,
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    pub fn revoke_role(
+        deps: DepsMut,
+        info: MessageInfo,
+        role: Role,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        ownership::ensure_role(deps.storage, Role::Admin, &info.sender)?;
+
+        let address = deps.api.addr_validate(&address)?;
+        ROLES.remove(deps.storage, (role.as_str(), &address));
+
+        let resp = Response::new().add_event(
+            RevokeRoleEvent {
+                sender: info.sender,
+                role,
+                address,
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    pub fn add_accepted_denom(
+        deps: DepsMut,
+        info: MessageInfo,
+        minimal_donation: Coin,
+    ) -> Result<Response, ContractError> {
+        ownership::ensure_role(deps.storage, Role::Admin, &info.sender)?;
+
+        ACCEPTED_DENOMS.save(
+            deps.storage,
+            &minimal_donation.denom,
+            &minimal_donation.amount,
+        )?;
+
+        let resp = Response::new().add_event(
+            AddAcceptedDenomEvent {
+                sender: info.sender,
+                minimal_donation,
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    pub fn remove_accepted_denom(
+        deps: DepsMut,
+        info: MessageInfo,
+        denom: String,
+    ) -> Result<Response, ContractError> {
+        ownership::ensure_role(deps.storage, Role::Admin, &info.sender)?;
+
+        if !ACCEPTED_DENOMS.has(deps.storage, &denom) {
+            return Err(ContractError::UnsupportedDenom { denom });
+        }
+        ACCEPTED_DENOMS.remove(deps.storage, &denom);
+
+        let resp = Response::new().add_event(
+            RemoveAcceptedDenomEvent {
+                sender: info.sender,
+                denom,
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    pub fn add_cw20_token(
+        deps: DepsMut,
+        info: MessageInfo,
+        token: String,
+        minimal_donation: Uint128,
+    ) -> Result<Response, ContractError> {
+        ownership::ensure_role(deps.storage, Role::Admin, &info.sender)?;
+
+        let token = deps.api.addr_validate(&token)?;
+        CW20_TOKENS.save(deps.storage, &token, &minimal_donation)?;
+
+        let resp = Response::new().add_event(
+            AddCw20TokenEvent {
+                sender: info.sender,
+                token,
+                minimal_donation,
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    pub fn remove_cw20_token(
+        deps: DepsMut,
+        info: MessageInfo,
+        token: String,
+    ) -> Result<Response, ContractError> {
+        ownership::ensure_role(deps.storage, Role::Admin, &info.sender)?;
+
+        let token = deps.api.addr_validate(&token)?;
+        if !CW20_TOKENS.has(deps.storage, &token) {
+            return Err(ContractError::UnsupportedToken {
+                token: token.to_string(),
+            });
+        }
+        CW20_TOKENS.remove(deps.storage, &token);
+
+        let resp = Response::new().add_event(
+            RemoveCw20TokenEvent {
+                sender: info.sender,
+                token,
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    pub fn update_fee(
+        deps: DepsMut,
+        info: MessageInfo,
+        fee_bps: Option<u64>,
+        fee_collector: Option<String>,
+        fee_admin: Option<String>,
+    ) -> Result<Response, ContractError> {
+        let mut fee = FEE.may_load(deps.storage)?.unwrap_or_default();
+        if fee.admin.as_ref() != Some(&info.sender) {
+            return Err(ContractError::NotFeeAdmin {});
+        }
+
+        let mut event = FeeUpdateEvent::new(info.sender);
+        let optional =
+            |addr: &Option<Addr>| addr.as_ref().map_or("none".to_owned(), Addr::to_string);
+
+        if let Some(fee_bps) = fee_bps {
+            event.change("fee_bps", fee.fee_bps.to_string(), fee_bps.to_string());
+            fee.fee_bps = fee_bps;
+        }
+
+        if let Some(collector) = fee_collector {
+            let collector = Some(deps.api.addr_validate(&collector)?);
+            event.change(
+                "fee_collector",
+                optional(&fee.collector),
+                optional(&collector),
+            );
+            fee.collector = collector;
+        }
+
+        if let Some(admin) = fee_admin {
+            let admin = Some(deps.api.addr_validate(&admin)?);
+            event.change("fee_admin", optional(&fee.admin), optional(&admin));
+            fee.admin = admin;
+        }
+
+        fees::validate(&fee)?;
+        FEE.save(deps.storage, &fee)?;
+
+        Ok(Response::new().add_event(event.into()))
+    }
+
+    pub fn update_config(
+        deps: DepsMut,
+        info: MessageInfo,
+        update: ConfigUpdate,
+    ) -> Result<Response, ContractError> {
+        ownership::ensure_role(deps.storage, Role::Admin, &info.sender)?;
+
+        let mut event = ConfigUpdateEvent::new(info.sender);
+
+        let mut config = CONFIG.load(deps.storage)?;
+
+        if let Some(strict) = update.strict {
+            event.change("strict", config.strict.to_string(), strict.to_string());
+            config.strict = strict;
+        }
+
+        if let Some(refund) = update.refund {
+            event.change(
+                "refund",
+                to_json_string(&config.refund)?,
+                to_json_string(&refund)?,
+            );
+            config.refund = refund;
+        }
+
+        if let Some(collection) = update.reward_collection {
+            let collection = collection
+                .map(|collection| deps.api.addr_validate(&collection))
+                .transpose()?;
+            let optional =
+                |addr: &Option<Addr>| addr.as_ref().map_or("none".to_owned(), Addr::to_string);
+            event.change(
+                "reward_collection",
+                optional(&config.reward_collection),
+                optional(&collection),
+            );
+            config.reward_collection = collection;
+        }
+
+        if let Some(max_length) = update.max_memo_length {
+            event.change(
+                "max_memo_length",
+                config.max_memo_length.to_string(),
+                max_length.to_string(),
+            );
+            config.max_memo_length = max_length;
+        }
+
+        if let Some(limit) = update.rate_limit {
+            if let Some(limit) = &limit {
+                rate_limit::validate(limit)?;
+            }
+            event.change(
+                "rate_limit",
+                to_json_string(&config.rate_limit)?,
+                to_json_string(&limit)?,
+            );
+            config.rate_limit = limit;
+        }
+
+        if let Some(mode) = update.access_mode {
+            event.change(
+                "access_mode",
+                to_json_string(&config.access_mode)?,
+                to_json_string(&mode)?,
+            );
+            config.access_mode = mode;
+        }
+
+        if let Some(policy) = update.approval_policy {
+            let policy = policy
+                .map(|policy| approvals::validate(deps.api, policy))
+                .transpose()?;
+            event.change(
+                "approval_policy",
+                to_json_string(&config.approval_policy)?,
+                to_json_string(&policy)?,
+            );
+            config.approval_policy = policy;
+        }
+
+        if let Some(governance) = update.governance {
+            if let Some(governance) = &governance {
+                governance::validate(governance)?;
+            }
+            event.change(
+                "governance",
+                to_json_string(&config.governance)?,
+                to_json_string(&governance)?,
+            );
+            config.governance = governance;
+        }
+
+        if let Some(milestones) = update.milestones {
+            rewards::validate(&milestones)?;
+            event.change(
+                "milestones",
+                to_json_string(&config.milestones)?,
+                to_json_string(&milestones)?,
+            );
+            config.milestones = milestones;
+        }
+
+        CONFIG.save(deps.storage, &config)?;
+
+        if let Some(accepted_denoms) = update.accepted_denoms {
+            let old = ACCEPTED_DENOMS
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (denom, _) in &old {
+                ACCEPTED_DENOMS.remove(deps.storage, denom);
+            }
+
+            for coin in &accepted_denoms {
+                if ACCEPTED_DENOMS.has(deps.storage, &coin.denom) {
+                    return Err(ContractError::DuplicateDenom {
+                        denom: coin.denom.clone(),
+                    });
+                }
+                ACCEPTED_DENOMS.save(deps.storage, &coin.denom, &coin.amount)?;
+            }
+
+            event.change(
+                "accepted_denoms",
+                join_coins(
+                    old.into_iter()
+                        .map(|(denom, amount)| Coin { denom, amount }),
+                ),
+                join_coins(accepted_denoms),
+            );
+        }
+
+        if let Some(cw20_tokens) = update.cw20_tokens {
+            let old = CW20_TOKENS
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (token, _) in &old {
+                CW20_TOKENS.remove(deps.storage, token);
+            }
+
+            let mut new = vec![];
+            for coin in cw20_tokens {
+                let token = deps.api.addr_validate(&coin.address)?;
+                if CW20_TOKENS.has(deps.storage, &token) {
+                    return Err(ContractError::DuplicateDenom {
+                        denom: cw20_denom(&token),
+                    });
+                }
+                CW20_TOKENS.save(deps.storage, &token, &coin.amount)?;
+                new.push((token, coin.amount));
+            }
+
+            let cw20_coins = |tokens: Vec<(Addr, Uint128)>| {
+                join_coins(tokens.into_iter().map(|(token, amount)| Coin {
+                    denom: cw20_denom(&token),
+                    amount,
+                }))
+            };
+            event.change("cw20_tokens", cw20_coins(old), cw20_coins(new));
+        }
+
+        Ok(Response::new().add_event(event.into()))
+    }
+
+    /// Splits `funds` into the part kept by the contract and the part sent back
+    fn split_refund(
+        policy: &RefundPolicy,
+        accepted: &[(String, Uint128)],
+        funds: &[Coin],
+    ) -> (Vec<Coin>, Vec<Coin>) {
+        let mut kept = vec![];
+        let mut refunded = vec![];
+
+        for coin in funds {
+            let minimal = accepted
+                .iter()
+                .find(|(denom, _)| *denom == coin.denom)
+                .map(|(_, minimal)| *minimal);
+
+            let cap = match (minimal, &policy.cap) {
+                (None, _) if policy.unsupported_denoms => Uint128::zero(),
+                (None, _) | (Some(_), DonationCap::Uncapped {}) => coin.amount,
+                (Some(minimal), DonationCap::Minimal {}) => minimal,
+                (Some(_), DonationCap::Maximum { amounts }) => amounts
+                    .iter()
+                    .find(|max| max.denom == coin.denom)
+                    .map(|max| max.amount)
+                    .unwrap_or(coin.amount),
+            };
+
+            let keep = std::cmp::min(coin.amount, cap);
+            if !keep.is_zero() {
+                kept.push(Coin {
+                    denom: coin.denom.clone(),
+                    amount: keep,
+                });
+            }
+            if keep < coin.amount {
+                refunded.push(Coin {
+                    denom: coin.denom.clone(),
+                    amount: coin.amount - keep,
+                });
+            }
+        }
+
+        (kept, refunded)
+    }
+
+    /// Explains why `funds` are not enough to be counted
+    fn rejection(accepted: &[(String, Uint128)], funds: &[Coin]) -> ContractError {
+        let Some(sent) = funds.first() else {
+            return ContractError::NoFunds {};
+        };
+
+        for coin in funds {
+            if !accepted.iter().any(|(denom, _)| *denom == coin.denom) {
+                return ContractError::UnsupportedDenom {
+                    denom: coin.denom.clone(),
+                };
+            }
+        }
+
+        let required = accepted
+            .iter()
+            .find(|(denom, _)| *denom == sent.denom)
+            .map(|(denom, amount)| Coin {
+                denom: denom.clone(),
+                amount: *amount,
+            })
+            .unwrap_or_default();
+
+        ContractError::InsufficientDonation {
+            required,
+            sent: sent.clone(),
+        }
+    }
+
+    fn load_counter(
+        storage: &dyn Storage,
+        counter_id: &str,
+    ) -> Result<CounterState, ContractError> {
+        COUNTERS
+            .may_load(storage, counter_id)?
+            .ok_or_else(|| ContractError::CounterNotFound {
+                counter_id: counter_id.to_owned(),
+            })
+    }
+
+    /// Stores a withdrawal exceeding the limits until enough approvers approve it
+    fn request_withdrawal(
+        storage: &mut dyn Storage,
+        env: &Env,
+        policy: &ApprovalPolicy,
+        sender: Addr,
+        target: WithdrawalTarget,
+        amount: Vec<Coin>,
+    ) -> Result<Response, ContractError> {
+        let id = PENDING_WITHDRAWALS
+            .keys(storage, None, None, Order::Descending)
+            .next()
+            .transpose()?
+            .map_or(0, |last| last + 1);
+
+        PENDING_WITHDRAWALS.save(
+            storage,
+            id,
+            &PendingWithdrawal {
+                requester: sender.clone(),
+                target: target.clone(),
+                amount: amount.clone(),
+                expires: policy.expiry.after(&env.block),
+                approvals: vec![],
+                executed: false,
+            },
+        )?;
+
+        let resp = Response::new().add_event(
+            WithdrawalRequestEvent {
+                sender,
+                id,
+                target,
+                amount,
+            }
+            .into(),
+        );
+
+        Ok(resp)
+    }
+
+    /// Loads a pending withdrawal which can still be approved and executed
+    fn load_open_withdrawal(
+        storage: &dyn Storage,
+        env: &Env,
+        id: u64,
+    ) -> Result<PendingWithdrawal, ContractError> {
+        let pending = PENDING_WITHDRAWALS
+            .may_load(storage, id)?
+            .ok_or(ContractError::WithdrawalNotFound { id })?;
+
+        match approvals::status(&pending, &env.block) {
+            PendingWithdrawalStatus::Open => Ok(pending),
+            PendingWithdrawalStatus::Executed => Err(ContractError::WithdrawalExecuted { id }),
+            PendingWithdrawalStatus::Expired => Err(ContractError::WithdrawalExpired { id }),
+        }
+    }
+
+    /// Fails unless the contract holds `amount`, cw20 tokens included
+    fn ensure_balance(deps: Deps, env: &Env, amount: &[Coin]) -> Result<(), ContractError> {
+        for coin in amount {
+            let balance = match coin.denom.strip_prefix(CW20_DENOM_PREFIX) {
+                Some(token) => cw20_balance(deps, env, &Addr::unchecked(token))?,
+                None => {
+                    deps.querier
+                        .query_balance(&env.contract.address, &coin.denom)?
+                        .amount
+                }
+            };
+            if balance < coin.amount {
+                return Err(ContractError::InsufficientBalance {
+                    required: coin.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// ICS-20 transfers of `amount`, refunded after `timeout` seconds
+    fn ibc_transfer_msgs(
+        env: &Env,
+        channel_id: &str,
+        remote_address: &str,
+        amount: &[Coin],
+        timeout: u64,
+    ) -> Vec<IbcMsg> {
+        let timeout = IbcTimeout::with_timestamp(env.block.time.plus_seconds(timeout));
+        amount
+            .iter()
+            .map(|coin| IbcMsg::Transfer {
+                channel_id: channel_id.to_owned(),
+                to_address: remote_address.to_owned(),
+                amount: coin.clone(),
+                timeout: timeout.clone(),
+            })
+            .collect()
+    }
+
+    /// Payouts splitting `amount` between the beneficiaries, with the total actually paid out
+    fn distribution_msgs(
+        storage: &mut dyn Storage,
+        env: &Env,
+        amount: Vec<Coin>,
+        reply_on: ReplyOn,
+    ) -> Result<(Vec<SubMsg>, Vec<Coin>), ContractError> {
+        let list = BENEFICIARIES
+            .may_load(storage)?
+            .ok_or(ContractError::NoBeneficiaries {})?;
+
+        let mut msgs = vec![];
+        let mut total = vec![];
+        for (receiver, share) in beneficiaries::split(&list, &amount) {
+            let (native, cw20) = split_cw20(share);
+            let payouts = payouts(receiver.as_str(), native, cw20)?;
+            add_coins(&mut total, &payout_total(&payouts));
+            msgs.extend(withdrawal_msgs(
+                storage,
+                env,
+                &receiver,
+                payouts,
+                reply_on.clone(),
+            )?);
+        }
+
+        Ok((msgs, total))
+    }
+
+    fn load_proposal(storage: &dyn Storage, id: u64) -> Result<Proposal, ContractError> {
+        PROPOSALS
+            .may_load(storage, id)?
+            .ok_or(ContractError::ProposalNotFound { id })
+    }
+
+    /// Denom under which cw20 donations are recorded in the donor ledger
+    fn cw20_denom(token: &Addr) -> String {
+        format!("{CW20_DENOM_PREFIX}{token}")
+    }
+
+    /// Separates cw20 amounts recorded with `cw20_denom` from native coins
+    fn split_cw20(coins: Vec<Coin>) -> (Vec<Coin>, Vec<Cw20CoinVerified>) {
+        let mut native = vec![];
+        let mut cw20 = vec![];
+
+        for coin in coins {
+            match coin.denom.strip_prefix(CW20_DENOM_PREFIX) {
+                Some(token) => cw20.push(Cw20CoinVerified {
+                    address: Addr::unchecked(token),
+                    amount: coin.amount,
+                }),
+                None => native.push(coin),
+            }
+        }
+
+        (native, cw20)
+    }
+
+    fn cw20_balance(deps: Deps, env: &Env, token: &Addr) -> StdResult<Uint128> {
+        let resp: BalanceResponse = deps.querier.query_wasm_smart(
+            token,
+            &Cw20QueryMsg::Balance {
+                address: env.contract.address.to_string(),
+            },
+        )?;
+        Ok(resp.balance)
+    }
+
+    /// Balances of all accepted cw20 tokens
+    fn cw20_balances(deps: Deps, env: &Env) -> StdResult<Vec<Cw20CoinVerified>> {
+        CW20_TOKENS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .map(|token| {
+                let address = token?;
+                let amount = cw20_balance(deps, env, &address)?;
+                Ok(Cw20CoinVerified { address, amount })
+            })
+            .collect()
+    }
+
+    /// Messages sending the non-zero amounts to `receiver`, each with the coins it pays out
+    fn payouts(
+        receiver: &str,
+        balance: Vec<Coin>,
+        cw20_balance: Vec<Cw20CoinVerified>,
+    ) -> StdResult<Vec<(CosmosMsg, Vec<Coin>)>> {
+        let balance: Vec<_> = balance
+            .into_iter()
+            .filter(|coin| !coin.amount.is_zero())
+            .collect();
+
+        let mut payouts = vec![];
+        if !balance.is_empty() {
+            let msg = BankMsg::Send {
+                to_address: receiver.to_owned(),
+                amount: balance.clone(),
+            };
+            payouts.push((msg.into(), balance));
+        }
+
+        for coin in cw20_balance {
+            if coin.amount.is_zero() {
+                continue;
+            }
+
+            let msg = WasmMsg::Execute {
+                contract_addr: coin.address.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: receiver.to_owned(),
+                    amount: coin.amount,
+                })?,
+                funds: vec![],
+            };
+            let amount = Coin {
+                denom: cw20_denom(&coin.address),
+                amount: coin.amount,
+            };
+            payouts.push((msg.into(), vec![amount]));
+        }
+
+        Ok(payouts)
+    }
+
+    fn payout_total(payouts: &[(CosmosMsg, Vec<Coin>)]) -> Vec<Coin> {
+        payouts
+            .iter()
+            .flat_map(|(_, amount)| amount.iter().cloned())
+            .collect()
+    }
+
+    /// Logs each payout as a pending withdrawal, its outcome is recorded on reply. With
+    /// `ReplyOn::Success` a failed payout reverts the whole call instead.
+    fn withdrawal_msgs(
+        storage: &mut dyn Storage,
+        env: &Env,
+        receiver: &Addr,
+        payouts: Vec<(CosmosMsg, Vec<Coin>)>,
+        reply_on: ReplyOn,
+    ) -> StdResult<Vec<SubMsg>> {
+        let first_id = WITHDRAWALS
+            .keys(storage, None, None, Order::Descending)
+            .next()
+            .transpose()?
+            .map_or(0, |last| last + 1);
+
+        let mut msgs = vec![];
+        for (id, (msg, amount)) in (first_id..).zip(payouts) {
+            WITHDRAWALS.save(
+                storage,
+                id,
+                &WithdrawalRecord {
+                    receiver: receiver.clone(),
+                    amount,
+                    height: env.block.height,
+                    outcome: WithdrawalOutcome::Pending {},
+                },
+            )?;
+            msgs.push(SubMsg {
+                id,
+                msg,
+                gas_limit: None,
+                reply_on: reply_on.clone(),
+            });
+        }
+
+        Ok(msgs)
+    }
+
+    fn ensure_memo_length(config: &Config, memo: Option<&str>) -> Result<(), ContractError> {
+        let length = memo.map_or(0, str::len);
+        if length > config.max_memo_length as usize {
+            return Err(ContractError::MemoTooLong {
+                length,
+                max_length: config.max_memo_length,
+            });
+        }
+        Ok(())
+    }
+
+    fn record_donation(
+        storage: &mut dyn Storage,
+        env: &Env,
+        donor: &Addr,
+        counter_id: &str,
+        funds: &[Coin],
+        counted: bool,
+        memo: Option<String>,
+    ) -> StdResult<()> {
+        let height = env.block.height;
+        governance::record_weight(storage, height, donor, funds)?;
+        let new_donor = !DONORS.has(storage, donor);
+        stats::record_donation(storage, height, new_donor, funds, counted)?;
+
+        let donations = donations();
+        let id = donations
+            .keys(storage, None, None, Order::Descending)
+            .next()
+            .transpose()?
+            .map_or(0, |last| last + 1);
+        donations.save(
+            storage,
+            id,
+            &Donation {
+                donor: donor.clone(),
+                counter_id: counter_id.to_owned(),
+                amount: funds.to_vec(),
+                counted,
+                height,
+                memo,
+                memo_hidden: false,
+            },
+        )?;
+
+        DONORS.update(storage, donor, |record| -> StdResult<_> {
+            let mut record = record.unwrap_or(DonorRecord {
+                total: vec![],
+                donations: 0,
+                first_donation_height: height,
+                last_donation_height: height,
+            });
+
+            add_coins(&mut record.total, funds);
+
+            if counted {
+                record.donations += 1;
+            }
+            record.last_donation_height = height;
+
+            Ok(record)
+        })?;
+
+        Ok(())
+    }
+}
+
+pub mod reply {
+    use cosmwasm_std::{DepsMut, Event, Reply, Response, SubMsgResult};
+
+    use crate::error::ContractError;
+    use crate::events::join_coins;
+    use crate::msg::WithdrawalOutcome;
+    use crate::state::WITHDRAWALS;
+    use crate::stats;
+
+    /// Records the outcome of the withdrawal sent with the reply id
+    pub fn withdrawal(deps: DepsMut, reply: Reply) -> Result<Response, ContractError> {
+        let mut record = WITHDRAWALS
+            .may_load(deps.storage, reply.id)?
+            .ok_or(ContractError::UnknownReply { id: reply.id })?;
+
+        let mut event = Event::new("withdraw-result")
+            .add_attribute("id", reply.id.to_string())
+            .add_attribute("receiver", record.receiver.as_str())
+            .add_attribute("amount", join_coins(record.amount.clone()));
+
+        record.outcome = match reply.result {
+            SubMsgResult::Ok(_) => {
+                stats::record_withdrawal(deps.storage, record.height, &record.amount)?;
+                event = event.add_attribute("success", "true");
+                WithdrawalOutcome::Succeeded {}
+            }
+            SubMsgResult::Err(error) => {
+                event = event
+                    .add_attribute("success", "false")
+                    .add_attribute("error", &error);
+                WithdrawalOutcome::Failed { error }
+            }
+        };
+        WITHDRAWALS.save(deps.storage, reply.id, &record)?;
+
+        Ok(Response::new().add_event(event))
+    }
+
+    /// Reports a failed reward mint, the donation which reached the milestone is kept
+    pub fn reward(reply: Reply) -> Result<Response, ContractError> {
+        let SubMsgResult::Err(error) = reply.result else {
+            return Ok(Response::new());
+        };
+
+        let event = Event::new("reward-result")
+            .add_attribute("success", "false")
+            .add_attribute("error", error);
+
+        Ok(Response::new().add_event(event))
     }
 }
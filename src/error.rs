@@ -1,6 +1,9 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Coin, StdError};
+use cw_utils::Expiration;
 use thiserror::Error;
 
+use crate::msg::{Operation, Role};
+
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
     #[error("{0}")]
@@ -8,4 +11,191 @@ pub enum ContractError {
 
     #[error("Unauthorized - only {owner} can call it")]
     Unauthorized { owner: String },
+
+    #[error("Missing role: {role}")]
+    MissingRole { role: Role },
+
+    #[error("Ownership has been renounced")]
+    OwnershipRenounced {},
+
+    #[error("There is no pending ownership transfer")]
+    NoPendingOwner {},
+
+    #[error("Only {pending} can accept the ownership")]
+    NotPendingOwner { pending: String },
+
+    #[error("Ownership transfer expired")]
+    TransferExpired {},
+
+    #[error("No funds sent")]
+    NoFunds {},
+
+    #[error("Insufficient donation - required {required}, sent {sent}")]
+    InsufficientDonation { required: Coin, sent: Coin },
+
+    #[error("Denom {denom} is not accepted")]
+    UnsupportedDenom { denom: String },
+
+    #[error("Token {token} is not accepted")]
+    UnsupportedToken { token: String },
+
+    #[error("Denom {denom} is listed more than once")]
+    DuplicateDenom { denom: String },
+
+    #[error("Counter {counter_id} does not exist")]
+    CounterNotFound { counter_id: String },
+
+    #[error("Counter {counter_id} already exists")]
+    CounterExists { counter_id: String },
+
+    #[error("Counter id can't be empty")]
+    EmptyCounterId {},
+
+    #[error("Milestone interval can't be zero")]
+    InvalidMilestones {},
+
+    #[error("Beneficiary weights sum up to {total} basis points, expected 10000")]
+    InvalidWeights { total: u64 },
+
+    #[error("Beneficiary {address} is listed more than once")]
+    DuplicateBeneficiary { address: String },
+
+    #[error("No beneficiaries are set")]
+    NoBeneficiaries {},
+
+    #[error("Fee of {fee_bps} basis points exceeds the maximum of {max_fee_bps}")]
+    FeeTooHigh { fee_bps: u64, max_fee_bps: u64 },
+
+    #[error("Fee collector is required for a non-zero fee")]
+    MissingFeeCollector {},
+
+    #[error("Only the fee admin can change the fee")]
+    NotFeeAdmin {},
+
+    #[error("Channel {channel_id} is not allowed")]
+    ChannelNotAllowed { channel_id: String },
+
+    #[error("Quorum and threshold have to be above 0 and at most 1")]
+    InvalidGovernance {},
+
+    #[error("Governance is not enabled")]
+    GovernanceDisabled {},
+
+    #[error("Funds can only be spent through proposals")]
+    GovernanceEnabled {},
+
+    #[error("Proposal {id} does not exist")]
+    ProposalNotFound { id: u64 },
+
+    #[error("Voting on proposal {id} has ended")]
+    VotingClosed { id: u64 },
+
+    #[error("Already voted on proposal {id}")]
+    AlreadyVoted { id: u64 },
+
+    #[error("No voting weight, only donors of {denom} can vote")]
+    NoVotingWeight { denom: String },
+
+    #[error("Proposal {id} has not passed")]
+    ProposalNotPassed { id: u64 },
+
+    #[error("Insufficient balance - required {required}")]
+    InsufficientBalance { required: Coin },
+
+    #[error("Approver {address} is listed more than once")]
+    DuplicateApprover { address: String },
+
+    #[error("Approval threshold {threshold} has to be between 1 and the {approvers} approvers")]
+    InvalidApprovalThreshold { threshold: u32, approvers: u32 },
+
+    #[error("Only approvers can approve withdrawals")]
+    NotApprover {},
+
+    #[error("Rate limit windows have to allow at least one donation")]
+    InvalidRateLimit {},
+
+    #[error("Donation rate limit reached, retry after {retry_after}")]
+    RateLimited { retry_after: Expiration },
+
+    #[error("Memo has {length} bytes, at most {max_length} are allowed")]
+    MemoTooLong { length: usize, max_length: u32 },
+
+    #[error("Memo can't be logged, the whole donation is refunded")]
+    MemoNotLogged {},
+
+    #[error("Donation {id} does not exist")]
+    DonationNotFound { id: u64 },
+
+    #[error("Address {donor} is not allowed to donate")]
+    DonorNotAllowed { donor: String },
+
+    #[error("Operation paused: {operation}")]
+    Paused { operation: Operation },
+
+    #[error("Operation already paused: {operation}")]
+    AlreadyPaused { operation: Operation },
+
+    #[error("Operation not paused: {operation}")]
+    NotPaused { operation: Operation },
+
+    #[error("Pending withdrawal {id} does not exist")]
+    WithdrawalNotFound { id: u64 },
+
+    #[error("Pending withdrawal {id} has expired")]
+    WithdrawalExpired { id: u64 },
+
+    #[error("Pending withdrawal {id} was already executed")]
+    WithdrawalExecuted { id: u64 },
+
+    #[error("Already approved withdrawal {id}")]
+    AlreadyApproved { id: u64 },
+
+    #[error("No approval of withdrawal {id} to revoke")]
+    NotApproved { id: u64 },
+
+    #[error("Withdrawal {id} has {approvals} approvals, {threshold} needed")]
+    InsufficientApprovals {
+        id: u64,
+        approvals: u32,
+        threshold: u32,
+    },
+
+    #[error("Invalid campaign: {reason}")]
+    InvalidCampaign { reason: String },
+
+    #[error("Campaign has not started yet")]
+    CampaignNotStarted {},
+
+    #[error("Campaign has ended")]
+    CampaignEnded {},
+
+    #[error("Campaign has not finished yet")]
+    CampaignNotFinished {},
+
+    #[error("Campaign goal was not reached")]
+    CampaignGoalNotReached {},
+
+    #[error("Campaign goal was reached, donations are not refundable")]
+    CampaignGoalReached {},
+
+    #[error("Nothing to refund")]
+    NothingToRefund {},
+
+    #[error("Cannot migrate from {actual}, expected {expected}")]
+    InvalidContractName { expected: String, actual: String },
+
+    #[error("Cannot migrate from version {current} to older version {new}")]
+    CannotDowngrade { current: String, new: String },
+
+    #[error("Unexpected reply {id}")]
+    UnknownReply { id: u64 },
+
+    #[error("Invalid version: {0}")]
+    InvalidVersion(String),
+}
+
+impl From<semver::Error> for ContractError {
+    fn from(err: semver::Error) -> Self {
+        Self::InvalidVersion(err.to_string())
+    }
 }
@@ -0,0 +1,1226 @@
+//! Events emitted by the contract actions.
+//!
+//! Every event is built from a typed struct and can be parsed back from the emitted [`Event`],
+//! with or without the `wasm-` prefix added by the chain. Coin lists are formatted as comma
+//! separated coins, `none` if empty. Cw20 amounts use the `cw20:<token>` denom of the donor
+//! ledger.
+
+use std::str::FromStr;
+
+use cosmwasm_std::{from_json, to_json_string, Addr, Coin, Event, StdError, StdResult, Uint128};
+
+use crate::msg::{AccessListKind, Operation, Role, VoteOption, WithdrawalTarget};
+
+/// Donation received by a counter, native or cw20.
+///
+/// Attributes: `sender`, `counter_id`, `kept`, `refunded`, `fee`, `counter_before`,
+/// `counter_after`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DonateEvent {
+    pub sender: Addr,
+    pub counter_id: String,
+    /// Part of the donation kept by the contract
+    pub kept: Vec<Coin>,
+    /// Part of the donation sent back to the sender
+    pub refunded: Vec<Coin>,
+    /// Part of `kept` forwarded to the fee collector
+    pub fee: Vec<Coin>,
+    pub counter_before: u64,
+    /// Same as `counter_before` if the donation was not counted
+    pub counter_after: u64,
+}
+
+impl DonateEvent {
+    pub const TYPE: &'static str = "counting-donate";
+}
+
+impl From<DonateEvent> for Event {
+    fn from(event: DonateEvent) -> Self {
+        Event::new(DonateEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("counter_id", event.counter_id)
+            .add_attribute("kept", join_coins(event.kept))
+            .add_attribute("refunded", join_coins(event.refunded))
+            .add_attribute("fee", join_coins(event.fee))
+            .add_attribute("counter_before", event.counter_before.to_string())
+            .add_attribute("counter_after", event.counter_after.to_string())
+    }
+}
+
+impl TryFrom<&Event> for DonateEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            counter_id: attribute(event, "counter_id")?.to_owned(),
+            kept: parse_coins(attribute(event, "kept")?)?,
+            refunded: parse_coins(attribute(event, "refunded")?)?,
+            fee: parse_coins(attribute(event, "fee")?)?,
+            counter_before: parse(attribute(event, "counter_before")?)?,
+            counter_after: parse(attribute(event, "counter_after")?)?,
+        })
+    }
+}
+
+/// Counter value set by a resetter or the counter owner.
+///
+/// Attributes: `sender`, `counter_id`, `counter_before`, `counter_after`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResetEvent {
+    pub sender: Addr,
+    pub counter_id: String,
+    pub counter_before: u64,
+    pub counter_after: u64,
+}
+
+impl ResetEvent {
+    pub const TYPE: &'static str = "counting-reset";
+}
+
+impl From<ResetEvent> for Event {
+    fn from(event: ResetEvent) -> Self {
+        Event::new(ResetEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("counter_id", event.counter_id)
+            .add_attribute("counter_before", event.counter_before.to_string())
+            .add_attribute("counter_after", event.counter_after.to_string())
+    }
+}
+
+impl TryFrom<&Event> for ResetEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            counter_id: attribute(event, "counter_id")?.to_owned(),
+            counter_before: parse(attribute(event, "counter_before")?)?,
+            counter_after: parse(attribute(event, "counter_after")?)?,
+        })
+    }
+}
+
+/// Whole balance sent to the sender.
+///
+/// Attributes: `sender`, `receiver`, `amount`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WithdrawEvent {
+    pub sender: Addr,
+    /// Always the sender
+    pub receiver: Addr,
+    pub amount: Vec<Coin>,
+}
+
+impl WithdrawEvent {
+    pub const TYPE: &'static str = "counting-withdraw";
+}
+
+impl From<WithdrawEvent> for Event {
+    fn from(event: WithdrawEvent) -> Self {
+        Event::new(WithdrawEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("receiver", event.receiver)
+            .add_attribute("amount", join_coins(event.amount))
+    }
+}
+
+impl TryFrom<&Event> for WithdrawEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            receiver: Addr::unchecked(attribute(event, "receiver")?),
+            amount: parse_coins(attribute(event, "amount")?)?,
+        })
+    }
+}
+
+/// Funds sent to an arbitrary receiver.
+///
+/// Attributes: `sender`, `receiver`, `amount`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WithdrawToEvent {
+    pub sender: Addr,
+    pub receiver: Addr,
+    pub amount: Vec<Coin>,
+}
+
+impl WithdrawToEvent {
+    pub const TYPE: &'static str = "counting-withdraw-to";
+}
+
+impl From<WithdrawToEvent> for Event {
+    fn from(event: WithdrawToEvent) -> Self {
+        Event::new(WithdrawToEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("receiver", event.receiver)
+            .add_attribute("amount", join_coins(event.amount))
+    }
+}
+
+impl TryFrom<&Event> for WithdrawToEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            receiver: Addr::unchecked(attribute(event, "receiver")?),
+            amount: parse_coins(attribute(event, "amount")?)?,
+        })
+    }
+}
+
+/// Withdrawal exceeding the limits, waiting for approvals.
+///
+/// Attributes: `sender`, `id`, `target`, `amount`. The target is JSON encoded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WithdrawalRequestEvent {
+    pub sender: Addr,
+    /// Id of the pending withdrawal
+    pub id: u64,
+    pub target: WithdrawalTarget,
+    pub amount: Vec<Coin>,
+}
+
+impl WithdrawalRequestEvent {
+    pub const TYPE: &'static str = "counting-withdrawal-request";
+}
+
+impl From<WithdrawalRequestEvent> for Event {
+    fn from(event: WithdrawalRequestEvent) -> Self {
+        // Serializing a plain enum of strings and numbers can't fail
+        let target = to_json_string(&event.target).unwrap_or_default();
+        Event::new(WithdrawalRequestEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("id", event.id.to_string())
+            .add_attribute("target", target)
+            .add_attribute("amount", join_coins(event.amount))
+    }
+}
+
+impl TryFrom<&Event> for WithdrawalRequestEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            id: parse(attribute(event, "id")?)?,
+            target: from_json(attribute(event, "target")?)?,
+            amount: parse_coins(attribute(event, "amount")?)?,
+        })
+    }
+}
+
+/// Funds sent to another chain over ICS-20.
+///
+/// Attributes: `sender`, `channel_id`, `remote_address`, `amount`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WithdrawIbcEvent {
+    pub sender: Addr,
+    pub channel_id: String,
+    pub remote_address: String,
+    pub amount: Vec<Coin>,
+}
+
+impl WithdrawIbcEvent {
+    pub const TYPE: &'static str = "counting-withdraw-ibc";
+}
+
+impl From<WithdrawIbcEvent> for Event {
+    fn from(event: WithdrawIbcEvent) -> Self {
+        Event::new(WithdrawIbcEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("channel_id", event.channel_id)
+            .add_attribute("remote_address", event.remote_address)
+            .add_attribute("amount", join_coins(event.amount))
+    }
+}
+
+impl TryFrom<&Event> for WithdrawIbcEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            channel_id: attribute(event, "channel_id")?.to_owned(),
+            remote_address: attribute(event, "remote_address")?.to_owned(),
+            amount: parse_coins(attribute(event, "amount")?)?,
+        })
+    }
+}
+
+/// Funds of a passed proposal sent to its receiver.
+///
+/// Attributes: `sender`, `proposal_id`, `receiver`, `amount`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExecuteProposalEvent {
+    pub sender: Addr,
+    pub proposal_id: u64,
+    pub receiver: Addr,
+    pub amount: Vec<Coin>,
+}
+
+impl ExecuteProposalEvent {
+    pub const TYPE: &'static str = "counting-execute-proposal";
+}
+
+impl From<ExecuteProposalEvent> for Event {
+    fn from(event: ExecuteProposalEvent) -> Self {
+        Event::new(ExecuteProposalEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("proposal_id", event.proposal_id.to_string())
+            .add_attribute("receiver", event.receiver)
+            .add_attribute("amount", join_coins(event.amount))
+    }
+}
+
+impl TryFrom<&Event> for ExecuteProposalEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            proposal_id: parse(attribute(event, "proposal_id")?)?,
+            receiver: Addr::unchecked(attribute(event, "receiver")?),
+            amount: parse_coins(attribute(event, "amount")?)?,
+        })
+    }
+}
+
+/// Whole balance split between the beneficiaries, each payout is logged as a withdrawal.
+///
+/// Attributes: `sender`, `amount`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DistributeEvent {
+    pub sender: Addr,
+    /// Total amount sent, remainder included
+    pub amount: Vec<Coin>,
+}
+
+impl DistributeEvent {
+    pub const TYPE: &'static str = "counting-distribute";
+}
+
+impl From<DistributeEvent> for Event {
+    fn from(event: DistributeEvent) -> Self {
+        Event::new(DistributeEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("amount", join_coins(event.amount))
+    }
+}
+
+impl TryFrom<&Event> for DistributeEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            amount: parse_coins(attribute(event, "amount")?)?,
+        })
+    }
+}
+
+/// Single configuration field changed by `UpdateConfig`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfigChange {
+    pub field: String,
+    pub old: String,
+    pub new: String,
+}
+
+/// Configuration updated by an admin.
+///
+/// Attributes: `sender`, then `old_<field>` and `new_<field>` for every changed field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfigUpdateEvent {
+    pub sender: Addr,
+    pub changes: Vec<ConfigChange>,
+}
+
+impl ConfigUpdateEvent {
+    pub const TYPE: &'static str = "counting-config-update";
+
+    pub fn new(sender: Addr) -> Self {
+        Self {
+            sender,
+            changes: vec![],
+        }
+    }
+
+    pub fn change(&mut self, field: &str, old: impl Into<String>, new: impl Into<String>) {
+        self.changes.push(ConfigChange {
+            field: field.to_owned(),
+            old: old.into(),
+            new: new.into(),
+        });
+    }
+}
+
+impl From<ConfigUpdateEvent> for Event {
+    fn from(event: ConfigUpdateEvent) -> Self {
+        Event::new(ConfigUpdateEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attributes(change_attributes(event.changes))
+    }
+}
+
+impl TryFrom<&Event> for ConfigUpdateEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            changes: parse_changes(event)?,
+        })
+    }
+}
+
+/// Fee settings changed by the fee admin.
+///
+/// Attributes: `sender`, then `old_<field>` and `new_<field>` for every changed field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeUpdateEvent {
+    pub sender: Addr,
+    pub changes: Vec<ConfigChange>,
+}
+
+impl FeeUpdateEvent {
+    pub const TYPE: &'static str = "counting-fee-update";
+
+    pub fn new(sender: Addr) -> Self {
+        Self {
+            sender,
+            changes: vec![],
+        }
+    }
+
+    pub fn change(&mut self, field: &str, old: impl Into<String>, new: impl Into<String>) {
+        self.changes.push(ConfigChange {
+            field: field.to_owned(),
+            old: old.into(),
+            new: new.into(),
+        });
+    }
+}
+
+impl From<FeeUpdateEvent> for Event {
+    fn from(event: FeeUpdateEvent) -> Self {
+        Event::new(FeeUpdateEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attributes(change_attributes(event.changes))
+    }
+}
+
+impl TryFrom<&Event> for FeeUpdateEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            changes: parse_changes(event)?,
+        })
+    }
+}
+
+/// Accepted native denom added or its minimal donation changed.
+///
+/// Attributes: `sender`, `minimal_donation`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AddAcceptedDenomEvent {
+    pub sender: Addr,
+    pub minimal_donation: Coin,
+}
+
+impl AddAcceptedDenomEvent {
+    pub const TYPE: &'static str = "counting-add-accepted-denom";
+}
+
+impl From<AddAcceptedDenomEvent> for Event {
+    fn from(event: AddAcceptedDenomEvent) -> Self {
+        Event::new(AddAcceptedDenomEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("minimal_donation", event.minimal_donation.to_string())
+    }
+}
+
+impl TryFrom<&Event> for AddAcceptedDenomEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        let minimal_donation = parse_coins(attribute(event, "minimal_donation")?)?
+            .pop()
+            .ok_or_else(|| StdError::parse_err("Coin", "missing minimal donation"))?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            minimal_donation,
+        })
+    }
+}
+
+/// Native denom no longer accepted.
+///
+/// Attributes: `sender`, `denom`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemoveAcceptedDenomEvent {
+    pub sender: Addr,
+    pub denom: String,
+}
+
+impl RemoveAcceptedDenomEvent {
+    pub const TYPE: &'static str = "counting-remove-accepted-denom";
+}
+
+impl From<RemoveAcceptedDenomEvent> for Event {
+    fn from(event: RemoveAcceptedDenomEvent) -> Self {
+        Event::new(RemoveAcceptedDenomEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("denom", event.denom)
+    }
+}
+
+impl TryFrom<&Event> for RemoveAcceptedDenomEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            denom: attribute(event, "denom")?.to_owned(),
+        })
+    }
+}
+
+/// Cw20 token accepted or its minimal donation changed.
+///
+/// Attributes: `sender`, `token`, `minimal_donation`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AddCw20TokenEvent {
+    pub sender: Addr,
+    pub token: Addr,
+    pub minimal_donation: Uint128,
+}
+
+impl AddCw20TokenEvent {
+    pub const TYPE: &'static str = "counting-add-cw20-token";
+}
+
+impl From<AddCw20TokenEvent> for Event {
+    fn from(event: AddCw20TokenEvent) -> Self {
+        Event::new(AddCw20TokenEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("token", event.token)
+            .add_attribute("minimal_donation", event.minimal_donation.to_string())
+    }
+}
+
+impl TryFrom<&Event> for AddCw20TokenEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            token: Addr::unchecked(attribute(event, "token")?),
+            minimal_donation: parse(attribute(event, "minimal_donation")?)?,
+        })
+    }
+}
+
+/// Cw20 token no longer accepted.
+///
+/// Attributes: `sender`, `token`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemoveCw20TokenEvent {
+    pub sender: Addr,
+    pub token: Addr,
+}
+
+impl RemoveCw20TokenEvent {
+    pub const TYPE: &'static str = "counting-remove-cw20-token";
+}
+
+impl From<RemoveCw20TokenEvent> for Event {
+    fn from(event: RemoveCw20TokenEvent) -> Self {
+        Event::new(RemoveCw20TokenEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("token", event.token)
+    }
+}
+
+impl TryFrom<&Event> for RemoveCw20TokenEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            token: Addr::unchecked(attribute(event, "token")?),
+        })
+    }
+}
+
+/// Role granted to an address.
+///
+/// Attributes: `sender`, `role`, `address`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GrantRoleEvent {
+    pub sender: Addr,
+    pub role: Role,
+    pub address: Addr,
+}
+
+impl GrantRoleEvent {
+    pub const TYPE: &'static str = "counting-grant-role";
+}
+
+impl From<GrantRoleEvent> for Event {
+    fn from(event: GrantRoleEvent) -> Self {
+        Event::new(GrantRoleEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("role", event.role.as_str())
+            .add_attribute("address", event.address)
+    }
+}
+
+impl TryFrom<&Event> for GrantRoleEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            role: parse_variant(&Role::ALL, Role::as_str, attribute(event, "role")?)?,
+            address: Addr::unchecked(attribute(event, "address")?),
+        })
+    }
+}
+
+/// Role taken away from an address.
+///
+/// Attributes: `sender`, `role`, `address`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RevokeRoleEvent {
+    pub sender: Addr,
+    pub role: Role,
+    pub address: Addr,
+}
+
+impl RevokeRoleEvent {
+    pub const TYPE: &'static str = "counting-revoke-role";
+}
+
+impl From<RevokeRoleEvent> for Event {
+    fn from(event: RevokeRoleEvent) -> Self {
+        Event::new(RevokeRoleEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("role", event.role.as_str())
+            .add_attribute("address", event.address)
+    }
+}
+
+impl TryFrom<&Event> for RevokeRoleEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            role: parse_variant(&Role::ALL, Role::as_str, attribute(event, "role")?)?,
+            address: Addr::unchecked(attribute(event, "address")?),
+        })
+    }
+}
+
+/// Beneficiaries of `Distribute` replaced.
+///
+/// Attributes: `sender`, `beneficiaries` as comma separated `<address>:<weight_bps>`,
+/// `remainder`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetBeneficiariesEvent {
+    pub sender: Addr,
+    pub beneficiaries: Vec<(Addr, u64)>,
+    pub remainder: Addr,
+}
+
+impl SetBeneficiariesEvent {
+    pub const TYPE: &'static str = "counting-set-beneficiaries";
+}
+
+impl From<SetBeneficiariesEvent> for Event {
+    fn from(event: SetBeneficiariesEvent) -> Self {
+        let beneficiaries: Vec<_> = event
+            .beneficiaries
+            .iter()
+            .map(|(address, weight)| format!("{address}:{weight}"))
+            .collect();
+
+        Event::new(SetBeneficiariesEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("beneficiaries", beneficiaries.join(","))
+            .add_attribute("remainder", event.remainder)
+    }
+}
+
+impl TryFrom<&Event> for SetBeneficiariesEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        let beneficiaries = attribute(event, "beneficiaries")?
+            .split(',')
+            .map(|share| {
+                let (address, weight) = share.rsplit_once(':').ok_or_else(|| {
+                    StdError::parse_err("Beneficiary", format!("missing weight: {share}"))
+                })?;
+                Ok((Addr::unchecked(address), parse(weight)?))
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            beneficiaries,
+            remainder: Addr::unchecked(attribute(event, "remainder")?),
+        })
+    }
+}
+
+/// ICS-20 channel allowed for `WithdrawIbc`.
+///
+/// Attributes: `sender`, `channel_id`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AddIbcChannelEvent {
+    pub sender: Addr,
+    pub channel_id: String,
+}
+
+impl AddIbcChannelEvent {
+    pub const TYPE: &'static str = "counting-add-ibc-channel";
+}
+
+impl From<AddIbcChannelEvent> for Event {
+    fn from(event: AddIbcChannelEvent) -> Self {
+        Event::new(AddIbcChannelEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("channel_id", event.channel_id)
+    }
+}
+
+impl TryFrom<&Event> for AddIbcChannelEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            channel_id: attribute(event, "channel_id")?.to_owned(),
+        })
+    }
+}
+
+/// ICS-20 channel no longer allowed for `WithdrawIbc`.
+///
+/// Attributes: `sender`, `channel_id`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemoveIbcChannelEvent {
+    pub sender: Addr,
+    pub channel_id: String,
+}
+
+impl RemoveIbcChannelEvent {
+    pub const TYPE: &'static str = "counting-remove-ibc-channel";
+}
+
+impl From<RemoveIbcChannelEvent> for Event {
+    fn from(event: RemoveIbcChannelEvent) -> Self {
+        Event::new(RemoveIbcChannelEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("channel_id", event.channel_id)
+    }
+}
+
+impl TryFrom<&Event> for RemoveIbcChannelEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            channel_id: attribute(event, "channel_id")?.to_owned(),
+        })
+    }
+}
+
+/// Spend proposal created, waiting for donor votes.
+///
+/// Attributes: `sender`, `proposal_id`, `receiver`, `amount`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpendProposalEvent {
+    pub sender: Addr,
+    pub proposal_id: u64,
+    pub receiver: Addr,
+    pub amount: Vec<Coin>,
+}
+
+impl SpendProposalEvent {
+    pub const TYPE: &'static str = "counting-spend-proposal";
+}
+
+impl From<SpendProposalEvent> for Event {
+    fn from(event: SpendProposalEvent) -> Self {
+        Event::new(SpendProposalEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("proposal_id", event.proposal_id.to_string())
+            .add_attribute("receiver", event.receiver)
+            .add_attribute("amount", join_coins(event.amount))
+    }
+}
+
+impl TryFrom<&Event> for SpendProposalEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            proposal_id: parse(attribute(event, "proposal_id")?)?,
+            receiver: Addr::unchecked(attribute(event, "receiver")?),
+            amount: parse_coins(attribute(event, "amount")?)?,
+        })
+    }
+}
+
+/// Donor vote on a spend proposal.
+///
+/// Attributes: `sender`, `proposal_id`, `vote`, `weight`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VoteEvent {
+    pub sender: Addr,
+    pub proposal_id: u64,
+    pub vote: VoteOption,
+    pub weight: Uint128,
+}
+
+impl VoteEvent {
+    pub const TYPE: &'static str = "counting-vote";
+}
+
+impl From<VoteEvent> for Event {
+    fn from(event: VoteEvent) -> Self {
+        Event::new(VoteEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("proposal_id", event.proposal_id.to_string())
+            .add_attribute("vote", event.vote.as_str())
+            .add_attribute("weight", event.weight.to_string())
+    }
+}
+
+impl TryFrom<&Event> for VoteEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            proposal_id: parse(attribute(event, "proposal_id")?)?,
+            vote: parse_variant(
+                &VoteOption::ALL,
+                VoteOption::as_str,
+                attribute(event, "vote")?,
+            )?,
+            weight: parse(attribute(event, "weight")?)?,
+        })
+    }
+}
+
+/// Approval given to a pending withdrawal.
+///
+/// Attributes: `sender`, `id`, `approvals`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ApproveWithdrawalEvent {
+    pub sender: Addr,
+    /// Id of the pending withdrawal
+    pub id: u64,
+    /// Approvals of current approvers after this one
+    pub approvals: u32,
+}
+
+impl ApproveWithdrawalEvent {
+    pub const TYPE: &'static str = "counting-approve-withdrawal";
+}
+
+impl From<ApproveWithdrawalEvent> for Event {
+    fn from(event: ApproveWithdrawalEvent) -> Self {
+        Event::new(ApproveWithdrawalEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("id", event.id.to_string())
+            .add_attribute("approvals", event.approvals.to_string())
+    }
+}
+
+impl TryFrom<&Event> for ApproveWithdrawalEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            id: parse(attribute(event, "id")?)?,
+            approvals: parse(attribute(event, "approvals")?)?,
+        })
+    }
+}
+
+/// Approval of a pending withdrawal taken back.
+///
+/// Attributes: `sender`, `id`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RevokeApprovalEvent {
+    pub sender: Addr,
+    /// Id of the pending withdrawal
+    pub id: u64,
+}
+
+impl RevokeApprovalEvent {
+    pub const TYPE: &'static str = "counting-revoke-approval";
+}
+
+impl From<RevokeApprovalEvent> for Event {
+    fn from(event: RevokeApprovalEvent) -> Self {
+        Event::new(RevokeApprovalEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("id", event.id.to_string())
+    }
+}
+
+impl TryFrom<&Event> for RevokeApprovalEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            id: parse(attribute(event, "id")?)?,
+        })
+    }
+}
+
+/// Addresses added to one of the access lists.
+///
+/// Attributes: `sender`, `list`, `addresses`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AddToAccessListEvent {
+    pub sender: Addr,
+    pub list: AccessListKind,
+    pub addresses: Vec<Addr>,
+}
+
+impl AddToAccessListEvent {
+    pub const TYPE: &'static str = "counting-add-to-access-list";
+}
+
+impl From<AddToAccessListEvent> for Event {
+    fn from(event: AddToAccessListEvent) -> Self {
+        Event::new(AddToAccessListEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("list", event.list.as_str())
+            .add_attribute("addresses", join_addrs(event.addresses))
+    }
+}
+
+impl TryFrom<&Event> for AddToAccessListEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            list: parse_variant(
+                &AccessListKind::ALL,
+                AccessListKind::as_str,
+                attribute(event, "list")?,
+            )?,
+            addresses: parse_addrs(attribute(event, "addresses")?),
+        })
+    }
+}
+
+/// Addresses removed from one of the access lists.
+///
+/// Attributes: `sender`, `list`, `addresses`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RemoveFromAccessListEvent {
+    pub sender: Addr,
+    pub list: AccessListKind,
+    pub addresses: Vec<Addr>,
+}
+
+impl RemoveFromAccessListEvent {
+    pub const TYPE: &'static str = "counting-remove-from-access-list";
+}
+
+impl From<RemoveFromAccessListEvent> for Event {
+    fn from(event: RemoveFromAccessListEvent) -> Self {
+        Event::new(RemoveFromAccessListEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("list", event.list.as_str())
+            .add_attribute("addresses", join_addrs(event.addresses))
+    }
+}
+
+impl TryFrom<&Event> for RemoveFromAccessListEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            list: parse_variant(
+                &AccessListKind::ALL,
+                AccessListKind::as_str,
+                attribute(event, "list")?,
+            )?,
+            addresses: parse_addrs(attribute(event, "addresses")?),
+        })
+    }
+}
+
+/// Donation memo hidden by an admin.
+///
+/// Attributes: `sender`, `id`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HideMemoEvent {
+    pub sender: Addr,
+    /// Id of the logged donation
+    pub id: u64,
+}
+
+impl HideMemoEvent {
+    pub const TYPE: &'static str = "counting-hide-memo";
+}
+
+impl From<HideMemoEvent> for Event {
+    fn from(event: HideMemoEvent) -> Self {
+        Event::new(HideMemoEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("id", event.id.to_string())
+    }
+}
+
+impl TryFrom<&Event> for HideMemoEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            id: parse(attribute(event, "id")?)?,
+        })
+    }
+}
+
+/// Operation paused by a guardian.
+///
+/// Attributes: `sender`, `operation`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PauseEvent {
+    pub sender: Addr,
+    pub operation: Operation,
+}
+
+impl PauseEvent {
+    pub const TYPE: &'static str = "counting-pause";
+}
+
+impl From<PauseEvent> for Event {
+    fn from(event: PauseEvent) -> Self {
+        Event::new(PauseEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("operation", event.operation.as_str())
+    }
+}
+
+impl TryFrom<&Event> for PauseEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            operation: parse_variant(
+                &Operation::ALL,
+                Operation::as_str,
+                attribute(event, "operation")?,
+            )?,
+        })
+    }
+}
+
+/// Paused operation resumed by a guardian.
+///
+/// Attributes: `sender`, `operation`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnpauseEvent {
+    pub sender: Addr,
+    pub operation: Operation,
+}
+
+impl UnpauseEvent {
+    pub const TYPE: &'static str = "counting-unpause";
+}
+
+impl From<UnpauseEvent> for Event {
+    fn from(event: UnpauseEvent) -> Self {
+        Event::new(UnpauseEvent::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("operation", event.operation.as_str())
+    }
+}
+
+impl TryFrom<&Event> for UnpauseEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        ensure_type(event, Self::TYPE)?;
+        Ok(Self {
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            operation: parse_variant(
+                &Operation::ALL,
+                Operation::as_str,
+                attribute(event, "operation")?,
+            )?,
+        })
+    }
+}
+
+/// Formats coins as a comma separated list, attribute values can't be empty
+pub(crate) fn join_coins(coins: impl IntoIterator<Item = Coin>) -> String {
+    let coins: Vec<_> = coins.into_iter().map(|coin| coin.to_string()).collect();
+    if coins.is_empty() {
+        return "none".to_owned();
+    }
+    coins.join(",")
+}
+
+/// Reverse of `join_coins`
+fn parse_coins(value: &str) -> StdResult<Vec<Coin>> {
+    if value == "none" {
+        return Ok(vec![]);
+    }
+
+    value
+        .split(',')
+        .map(|coin| {
+            let split = coin
+                .find(|c: char| !c.is_ascii_digit())
+                .ok_or_else(|| StdError::parse_err("Coin", format!("missing denom: {coin}")))?;
+            let (amount, denom) = coin.split_at(split);
+            Ok(Coin {
+                denom: denom.to_owned(),
+                amount: Uint128::from_str(amount)?,
+            })
+        })
+        .collect()
+}
+
+/// Formats addresses as a comma separated list, attribute values can't be empty
+fn join_addrs(addrs: Vec<Addr>) -> String {
+    if addrs.is_empty() {
+        return "none".to_owned();
+    }
+    addrs.iter().map(Addr::as_str).collect::<Vec<_>>().join(",")
+}
+
+/// Reverse of `join_addrs`
+fn parse_addrs(value: &str) -> Vec<Addr> {
+    if value == "none" {
+        return vec![];
+    }
+    value.split(',').map(Addr::unchecked).collect()
+}
+
+/// `old_<field>` and `new_<field>` attributes of every change
+fn change_attributes(changes: Vec<ConfigChange>) -> Vec<(String, String)> {
+    changes
+        .into_iter()
+        .flat_map(|change| {
+            [
+                (format!("old_{}", change.field), change.old),
+                (format!("new_{}", change.field), change.new),
+            ]
+        })
+        .collect()
+}
+
+/// Reverse of `change_attributes`
+fn parse_changes(event: &Event) -> StdResult<Vec<ConfigChange>> {
+    event
+        .attributes
+        .iter()
+        .filter_map(|attr| {
+            let field = attr.key.strip_prefix("old_")?;
+            Some((field, &attr.value))
+        })
+        .map(|(field, old)| {
+            Ok(ConfigChange {
+                field: field.to_owned(),
+                old: old.clone(),
+                new: attribute(event, &format!("new_{field}"))?.to_owned(),
+            })
+        })
+        .collect()
+}
+
+/// Finds the variant of `all` formatted as `value`
+fn parse_variant<T: Copy>(all: &[T], name: fn(&T) -> &'static str, value: &str) -> StdResult<T> {
+    all.iter()
+        .copied()
+        .find(|variant| name(variant) == value)
+        .ok_or_else(|| {
+            StdError::parse_err(
+                std::any::type_name::<T>(),
+                format!("unknown variant: {value}"),
+            )
+        })
+}
+
+fn ensure_type(event: &Event, ty: &str) -> StdResult<()> {
+    let actual = event.ty.strip_prefix("wasm-").unwrap_or(&event.ty);
+    if actual != ty {
+        return Err(StdError::parse_err(
+            ty,
+            format!("unexpected event {}", event.ty),
+        ));
+    }
+    Ok(())
+}
+
+fn attribute<'a>(event: &'a Event, key: &str) -> StdResult<&'a str> {
+    event
+        .attributes
+        .iter()
+        .find(|attr| attr.key == key)
+        .map(|attr| attr.value.as_str())
+        .ok_or_else(|| StdError::not_found(format!("attribute {key} of {}", event.ty)))
+}
+
+fn parse<T: FromStr>(value: &str) -> StdResult<T>
+where
+    T::Err: std::fmt::Display,
+{
+    value
+        .parse()
+        .map_err(|err| StdError::parse_err(std::any::type_name::<T>(), err))
+}
@@ -0,0 +1,88 @@
+use cosmwasm_std::{Addr, Coin, StdResult, Storage, Uint128};
+
+use crate::error::ContractError;
+use crate::state::{Fee, FEE, FEES_COLLECTED};
+
+/// Fees are basis points of the kept donation, so they can't exceed all of it
+const MAX_FEE_BPS: u64 = 10_000;
+
+/// Part of a counted donation forwarded to the fee collector
+pub struct Skim {
+    pub collector: Addr,
+    pub fee: Vec<Coin>,
+}
+
+pub fn validate(fee: &Fee) -> Result<(), ContractError> {
+    if fee.max_fee_bps > MAX_FEE_BPS {
+        return Err(ContractError::FeeTooHigh {
+            fee_bps: fee.max_fee_bps,
+            max_fee_bps: MAX_FEE_BPS,
+        });
+    }
+    if fee.fee_bps > fee.max_fee_bps {
+        return Err(ContractError::FeeTooHigh {
+            fee_bps: fee.fee_bps,
+            max_fee_bps: fee.max_fee_bps,
+        });
+    }
+    if fee.fee_bps > 0 && fee.collector.is_none() {
+        return Err(ContractError::MissingFeeCollector {});
+    }
+    Ok(())
+}
+
+/// Takes the fee share of every kept coin and adds it to the collected fees.
+/// Returns `None` if there is no fee to forward.
+pub fn skim(storage: &mut dyn Storage, kept: &[Coin]) -> StdResult<Option<Skim>> {
+    let Some(Fee {
+        fee_bps,
+        collector: Some(collector),
+        ..
+    }) = FEE.may_load(storage)?
+    else {
+        return Ok(None);
+    };
+
+    let fee: Vec<_> = kept
+        .iter()
+        .map(|coin| Coin {
+            denom: coin.denom.clone(),
+            amount: coin.amount.multiply_ratio(fee_bps, MAX_FEE_BPS),
+        })
+        .filter(|coin| !coin.amount.is_zero())
+        .collect();
+
+    if fee.is_empty() {
+        return Ok(None);
+    }
+
+    for coin in &fee {
+        FEES_COLLECTED.update(storage, &coin.denom, |collected| -> StdResult<_> {
+            Ok(collected.unwrap_or_default() + coin.amount)
+        })?;
+    }
+
+    Ok(Some(Skim { collector, fee }))
+}
+
+/// `kept` without the skimmed fee
+pub fn net(kept: &[Coin], skim: Option<&Skim>) -> Vec<Coin> {
+    let Some(skim) = skim else {
+        return kept.to_vec();
+    };
+
+    kept.iter()
+        .map(|coin| {
+            let fee = skim
+                .fee
+                .iter()
+                .find(|fee| fee.denom == coin.denom)
+                .map_or(Uint128::zero(), |fee| fee.amount);
+            Coin {
+                denom: coin.denom.clone(),
+                amount: coin.amount - fee,
+            }
+        })
+        .filter(|coin| !coin.amount.is_zero())
+        .collect()
+}
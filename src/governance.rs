@@ -0,0 +1,89 @@
+use cosmwasm_std::{Addr, BlockInfo, Coin, Decimal, StdResult, Storage, Uint128};
+
+use crate::error::ContractError;
+use crate::msg::{Governance, ProposalStatus};
+use crate::state::{Proposal, CONFIG, DONATION_TOTALS, DONATION_WEIGHTS};
+
+pub fn validate(governance: &Governance) -> Result<(), ContractError> {
+    let valid = |ratio: Decimal| !ratio.is_zero() && ratio <= Decimal::one();
+    if !valid(governance.quorum) || !valid(governance.threshold) {
+        return Err(ContractError::InvalidGovernance {});
+    }
+    Ok(())
+}
+
+/// Governance settings, failing if funds can't be spent through proposals
+pub fn load(storage: &dyn Storage) -> Result<Governance, ContractError> {
+    CONFIG
+        .load(storage)?
+        .governance
+        .ok_or(ContractError::GovernanceDisabled {})
+}
+
+/// Fails if funds can only be spent through proposals
+pub fn ensure_disabled(storage: &dyn Storage) -> Result<(), ContractError> {
+    if CONFIG.load(storage)?.governance.is_some() {
+        return Err(ContractError::GovernanceEnabled {});
+    }
+    Ok(())
+}
+
+/// Adds kept donations to the voting weight of `donor`, tracked for every denom so the
+/// governance denom can be changed later
+pub fn record_weight(
+    storage: &mut dyn Storage,
+    height: u64,
+    donor: &Addr,
+    funds: &[Coin],
+) -> StdResult<()> {
+    for coin in funds {
+        let add = |weight: Option<Uint128>| -> StdResult<_> {
+            Ok(weight.unwrap_or_default() + coin.amount)
+        };
+        DONATION_WEIGHTS.update(storage, (&coin.denom, donor), height, add)?;
+        DONATION_TOTALS.update(storage, &coin.denom, height, add)?;
+    }
+    Ok(())
+}
+
+/// Weight of `voter` at the beginning of the block the proposal was created in
+pub fn weight(storage: &dyn Storage, proposal: &Proposal, voter: &Addr) -> StdResult<Uint128> {
+    DONATION_WEIGHTS
+        .may_load_at_height(
+            storage,
+            (&proposal.governance.denom, voter),
+            proposal.start_height,
+        )
+        .map(Option::unwrap_or_default)
+}
+
+/// Sum of all voting weights at the beginning of the block at `height`
+pub fn total_weight(storage: &dyn Storage, denom: &str, height: u64) -> StdResult<Uint128> {
+    DONATION_TOTALS
+        .may_load_at_height(storage, denom, height)
+        .map(Option::unwrap_or_default)
+}
+
+pub fn status(proposal: &Proposal, block: &BlockInfo) -> ProposalStatus {
+    if proposal.executed {
+        ProposalStatus::Executed
+    } else if !proposal.expires.is_expired(block) {
+        ProposalStatus::Open
+    } else if passed(proposal) {
+        ProposalStatus::Passed
+    } else {
+        ProposalStatus::Rejected
+    }
+}
+
+fn passed(proposal: &Proposal) -> bool {
+    let Governance {
+        quorum, threshold, ..
+    } = proposal.governance;
+    let cast = proposal.yes + proposal.no + proposal.abstain;
+
+    !proposal.total_weight.is_zero()
+        && cast >= proposal.total_weight.mul_floor(quorum)
+        && !proposal.yes.is_zero()
+        && proposal.yes >= (proposal.yes + proposal.no).mul_floor(threshold)
+}
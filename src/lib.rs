@@ -1,17 +1,31 @@
-use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult,
+};
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 
 use error::ContractError;
-use msg::InstantiateMsg;
+use msg::{InstantiateMsg, MigrateMsg};
 
+mod access;
+mod approvals;
+mod beneficiaries;
+mod campaign;
 mod contract;
 mod error;
+pub mod events;
+mod fees;
+mod governance;
 pub mod msg;
 #[cfg(any(test, feature = "tests"))]
 pub mod multitest;
+mod ownership;
+mod pause;
+mod rate_limit;
+mod rewards;
 mod state;
+mod stats;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -19,8 +33,8 @@ pub fn instantiate(
     _env: Env,
     _info: MessageInfo,
     _msg: InstantiateMsg,
-) -> StdResult<Response> {
-    contract::instantiate(_deps, _info, _msg)
+) -> Result<Response, ContractError> {
+    contract::instantiate(_deps, _env, _info, _msg)
 }
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
@@ -33,10 +47,79 @@ pub fn execute(
     use msg::ExecMsg::*;
 
     match msg {
-        Donate {} => exec::donate(deps, info),
-        Reset { counter } => exec::reset(deps, info, counter),
+        Donate { counter_id, memo } => exec::donate(deps, _env, info, counter_id, memo),
+        Reset {
+            counter_id,
+            counter,
+        } => exec::reset(deps, _env, info, counter_id, counter),
+        CreateCounter {
+            counter_id,
+            minimal_donation,
+            owner,
+        } => exec::create_counter(deps, _env, info, counter_id, minimal_donation, owner),
         Withdraw {} => exec::withdraw(deps, _env, info),
-        WithdrawTo { receiver, funds } => exec::withdraw_to(deps, _env, info, receiver, funds),
+        WithdrawTo {
+            receiver,
+            funds,
+            cw20_funds,
+        } => exec::withdraw_to(deps, _env, info, receiver, funds, cw20_funds),
+        ProposeOwner { new_owner, expiry } => {
+            exec::propose_owner(deps, _env, info, new_owner, expiry)
+        }
+        AcceptOwnership {} => exec::accept_ownership(deps, _env, info),
+        CancelOwnershipTransfer {} => exec::cancel_ownership_transfer(deps, info),
+        RenounceOwnership {} => exec::renounce_ownership(deps, info),
+        GrantRole { role, address } => exec::grant_role(deps, info, role, address),
+        RevokeRole { role, address } => exec::revoke_role(deps, info, role, address),
+        AddAcceptedDenom { minimal_donation } => {
+            exec::add_accepted_denom(deps, info, minimal_donation)
+        }
+        RemoveAcceptedDenom { denom } => exec::remove_accepted_denom(deps, info, denom),
+        UpdateConfig(update) => exec::update_config(deps, info, *update),
+        Receive(msg) => exec::receive(deps, _env, info, msg),
+        AddCw20Token {
+            token,
+            minimal_donation,
+        } => exec::add_cw20_token(deps, info, token, minimal_donation),
+        RemoveCw20Token { token } => exec::remove_cw20_token(deps, info, token),
+        ClaimRefund {} => exec::claim_refund(deps, _env, info),
+        SetBeneficiaries {
+            beneficiaries,
+            remainder,
+        } => exec::set_beneficiaries(deps, info, beneficiaries, remainder),
+        Distribute {} => exec::distribute(deps, _env, info),
+        WithdrawIbc {
+            channel_id,
+            remote_address,
+            funds,
+            timeout,
+        } => exec::withdraw_ibc(deps, _env, info, channel_id, remote_address, funds, timeout),
+        AddIbcChannel { channel_id } => exec::add_ibc_channel(deps, info, channel_id),
+        RemoveIbcChannel { channel_id } => exec::remove_ibc_channel(deps, info, channel_id),
+        ApproveWithdrawal { id } => exec::approve_withdrawal(deps, _env, info, id),
+        RevokeApproval { id } => exec::revoke_approval(deps, _env, info, id),
+        ExecuteWithdrawal { id } => exec::execute_withdrawal(deps, _env, info, id),
+        SpendProposal {
+            receiver,
+            amount,
+            description,
+        } => exec::spend_proposal(deps, _env, info, receiver, amount, description),
+        Vote { proposal_id, vote } => exec::vote(deps, _env, info, proposal_id, vote),
+        ExecuteProposal { proposal_id } => exec::execute_proposal(deps, _env, info, proposal_id),
+        UpdateFee {
+            fee_bps,
+            fee_collector,
+            fee_admin,
+        } => exec::update_fee(deps, info, fee_bps, fee_collector, fee_admin),
+        HideMemo { id } => exec::hide_memo(deps, info, id),
+        AddToAccessList { list, addresses } => {
+            exec::add_to_access_list(deps, info, list, addresses)
+        }
+        RemoveFromAccessList { list, addresses } => {
+            exec::remove_from_access_list(deps, info, list, addresses)
+        }
+        Pause { operation } => exec::pause(deps, _env, info, operation),
+        Unpause { operation } => exec::unpause(deps, info, operation),
     }
 }
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -45,6 +128,74 @@ pub fn query(_deps: Deps, _env: Env, msg: msg::QueryMsg) -> StdResult<Binary> {
     use msg::QueryMsg::*;
 
     match msg {
-        Value {} => to_binary(&query::value(_deps)?),
+        Value { counter_id } => to_json_binary(&query::value(_deps, counter_id)?),
+        ValueAt { counter_id, height } => {
+            to_json_binary(&query::value_at(_deps, counter_id, height)?)
+        }
+        History {
+            counter_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query::history(_deps, counter_id, start_after, limit)?),
+        Counters { start_after, limit } => {
+            to_json_binary(&query::counters(_deps, start_after, limit)?)
+        }
+        Config {} => to_json_binary(&query::config(_deps)?),
+        Owner {} => to_json_binary(&query::owner(_deps)?),
+        Roles {} => to_json_binary(&query::roles(_deps)?),
+        HasRole { role, address } => to_json_binary(&query::has_role(_deps, role, address)?),
+        Donor { address } => to_json_binary(&query::donor(_deps, address)?),
+        Donors { start_after, limit } => to_json_binary(&query::donors(_deps, start_after, limit)?),
+        AcceptedDenoms {} => to_json_binary(&query::accepted_denoms(_deps)?),
+        Cw20Tokens {} => to_json_binary(&query::cw20_tokens(_deps)?),
+        Campaign {} => to_json_binary(&query::campaign(_deps, _env)?),
+        Withdrawals { start_after, limit } => {
+            to_json_binary(&query::withdrawals(_deps, start_after, limit)?)
+        }
+        Beneficiaries {} => to_json_binary(&query::beneficiaries(_deps)?),
+        Fee {} => to_json_binary(&query::fee(_deps)?),
+        IbcChannels {} => to_json_binary(&query::ibc_channels(_deps)?),
+        PendingWithdrawal { id } => to_json_binary(&query::pending_withdrawal(_deps, _env, id)?),
+        PendingWithdrawals { start_after, limit } => to_json_binary(&query::pending_withdrawals(
+            _deps,
+            _env,
+            start_after,
+            limit,
+        )?),
+        Proposal { id } => to_json_binary(&query::proposal(_deps, _env, id)?),
+        Proposals { start_after, limit } => {
+            to_json_binary(&query::proposals(_deps, _env, start_after, limit)?)
+        }
+        Votes {
+            proposal_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query::votes(_deps, proposal_id, start_after, limit)?),
+        Status {} => to_json_binary(&query::status(_deps)?),
+        Stats {} => to_json_binary(&query::stats(_deps)?),
+        Donations { start_after, limit } => {
+            to_json_binary(&query::donations(_deps, start_after, limit)?)
+        }
+        DonationsBy {
+            donor,
+            start_after,
+            limit,
+        } => to_json_binary(&query::donations_by(_deps, donor, start_after, limit)?),
+        AccessList {
+            list,
+            start_after,
+            limit,
+        } => to_json_binary(&query::access_list(_deps, list, start_after, limit)?),
+    }
+}
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    contract::migrate(deps, _env, msg)
+}
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        rewards::REWARD_REPLY_ID => contract::reply::reward(msg),
+        _ => contract::reply::withdrawal(deps, msg),
     }
 }
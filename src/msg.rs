@@ -1,33 +1,643 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_schema::QueryResponses;
-use cosmwasm_std::Coin;
+use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
+use cw20::{Cw20Coin, Cw20CoinVerified, Cw20ReceiveMsg};
+use cw_utils::Scheduled;
+use cw_utils::{Duration, Expiration};
+use serde::{Deserialize, Deserializer};
+
+/// Counter used by messages which don't name one
+pub const DEFAULT_COUNTER: &str = "default";
+
+/// Maximal memo length in bytes if not set on instantiation
+pub const DEFAULT_MAX_MEMO_LENGTH: u32 = 280;
 
 #[cw_serde]
+#[derive(Default)]
 pub struct InstantiateMsg {
+    /// Initial value of the default counter
     #[serde(default)]
     pub counter: u64,
     pub minimal_donation: Coin,
+    /// Further accepted denoms, each with its own minimal donation
+    #[serde(default)]
+    pub accepted_denoms: Vec<Coin>,
+    /// Reject donations which would not be counted instead of keeping them
+    #[serde(default)]
+    pub strict: bool,
+    #[serde(default)]
+    pub refund: RefundPolicy,
+    /// Collect donations toward a goal within a limited time
+    #[serde(default)]
+    pub campaign: Option<Campaign>,
+    /// cw721 collection minting rewards for donors reaching a milestone
+    #[serde(default)]
+    pub reward_collection: Option<String>,
+    #[serde(default)]
+    pub milestones: Milestones,
+    #[serde(default)]
+    pub governance: Option<Governance>,
+    #[serde(default)]
+    pub approval_policy: Option<ApprovalPolicy>,
+    #[serde(default)]
+    pub access_mode: AccessMode,
+    #[serde(default)]
+    pub rate_limit: Option<RateLimit>,
+    /// Defaults to `DEFAULT_MAX_MEMO_LENGTH`, 0 disables memos
+    #[serde(default)]
+    pub max_memo_length: Option<u32>,
+    /// Share of each counted donation forwarded to `fee_collector`, in basis points
+    #[serde(default)]
+    pub fee_bps: u64,
+    #[serde(default)]
+    pub fee_collector: Option<String>,
+    /// Upper bound of the fee which can't be changed later, `fee_bps` if not set
+    #[serde(default)]
+    pub max_fee_bps: Option<u64>,
+    /// Only address allowed to change the fee, it is fixed if not set
+    #[serde(default)]
+    pub fee_admin: Option<String>,
+}
+
+/// Counter values rewarding the donor who reached them
+#[cw_serde]
+#[derive(Default)]
+pub struct Milestones {
+    /// Every multiple of this value is a milestone
+    pub every: Option<u64>,
+    pub values: Vec<u64>,
+}
+
+/// Donors decide how funds are spent, they can't be withdrawn directly while it is enabled
+#[cw_serde]
+pub struct Governance {
+    /// Donations kept in this denom give weight to the votes
+    pub denom: String,
+    pub voting_period: Duration,
+    /// Part of the total weight which has to vote
+    pub quorum: Decimal,
+    /// Part of the yes and no votes which has to be yes
+    pub threshold: Decimal,
+}
+
+/// Withdrawals above the limits wait for approvals before they can be executed. This applies to
+/// `Distribute` and `WithdrawIbc` as well.
+///
+/// The limits cap the sum of direct withdrawals within `window`, so splitting a large withdrawal
+/// into several small ones still needs approvals.
+#[cw_serde]
+pub struct ApprovalPolicy {
+    pub approvers: Vec<String>,
+    /// Approvals needed to execute a withdrawal
+    pub threshold: u32,
+    /// Amounts per denom which can be withdrawn directly within `window`, any amount of denoms not
+    /// listed needs approvals. Cw20 tokens use the `cw20:<token>` denom.
+    pub limits: Vec<Coin>,
+    /// Direct withdrawals are summed up over this time, starting with the first one after the
+    /// previous window ended. Approved withdrawals don't count.
+    pub window: Duration,
+    /// Pending withdrawals can't be approved or executed after this time
+    pub expiry: Duration,
+}
+
+/// Where the funds of a pending withdrawal are sent
+#[cw_serde]
+#[derive(Eq)]
+pub enum WithdrawalTarget {
+    Address {
+        receiver: Addr,
+    },
+    /// ICS-20 transfer, the timeout is counted from the execution
+    Ibc {
+        channel_id: String,
+        remote_address: String,
+        timeout: u64,
+    },
+    /// Split between the beneficiaries set when the withdrawal is executed
+    Beneficiaries {},
+}
+
+/// Limits how often donations of a single address are counted
+#[cw_serde]
+pub struct RateLimit {
+    /// Time an address has to wait after a counted donation
+    pub cooldown: Option<Duration>,
+    pub window: Option<RateWindow>,
+    /// What happens to donations over the limit
+    #[serde(default)]
+    pub exceeded: RateLimitAction,
+}
+
+/// At most `max_donations` counted donations per address within `duration`, starting with the
+/// first donation of the window
+#[cw_serde]
+pub struct RateWindow {
+    pub duration: Duration,
+    pub max_donations: u32,
+}
+
+#[cw_serde]
+#[derive(Copy, Default)]
+pub enum RateLimitAction {
+    /// Fail the donation, returning the funds
+    #[default]
+    Reject,
+    /// Keep the donation without counting it
+    Uncounted,
+}
+
+#[cw_serde]
+#[derive(Copy)]
+pub enum VoteOption {
+    Yes,
+    No,
+    Abstain,
+}
+
+impl VoteOption {
+    pub const ALL: [VoteOption; 3] = [VoteOption::Yes, VoteOption::No, VoteOption::Abstain];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VoteOption::Yes => "yes",
+            VoteOption::No => "no",
+            VoteOption::Abstain => "abstain",
+        }
+    }
+}
+
+/// Extension of the reward NFTs
+#[cw_serde]
+pub struct RewardMetadata {
+    pub counter_id: String,
+    /// Counter value reached by the rewarded donation
+    pub counter: u64,
+    pub height: u64,
+}
+
+/// Timeout of `WithdrawIbc` transfers in seconds
+pub const IBC_DEFAULT_TIMEOUT: u64 = 600;
+
+/// Sum of the beneficiary weights
+pub const TOTAL_WEIGHT_BPS: u64 = 10_000;
+
+/// Receiver of a part of the distributed balance
+#[cw_serde]
+pub struct Beneficiary {
+    pub address: String,
+    /// Share of every denom in basis points
+    pub weight_bps: u64,
+}
+
+#[cw_serde]
+pub struct Campaign {
+    /// Amount to raise per denom, each of them has to be reached. Native denoms have to be
+    /// accepted, cw20 tokens use the `cw20:<token>` denom.
+    pub goal: Vec<Coin>,
+    /// Donations are accepted from this point on, immediately if not set
+    pub start: Option<Scheduled>,
+    /// Donations are accepted until this point
+    pub end: Scheduled,
+}
+
+/// Decides which part of a donation is sent back to the donor
+#[cw_serde]
+#[derive(Default)]
+pub struct RefundPolicy {
+    /// Refund coins in denoms which are not accepted
+    pub unsupported_denoms: bool,
+    pub cap: DonationCap,
+}
+
+/// Limits the amount kept from each accepted coin, the rest is refunded
+#[cw_serde]
+pub enum DonationCap {
+    Uncapped {},
+    /// Keep only the minimal donation of the denom
+    Minimal {},
+    /// Keep at most the listed amount per denom, denoms not listed are uncapped
+    Maximum {
+        amounts: Vec<Coin>,
+    },
+}
+
+impl Default for DonationCap {
+    fn default() -> Self {
+        Self::Uncapped {}
+    }
+}
+
+/// Who may donate, based on the addresses on the access lists
+#[cw_serde]
+#[derive(Copy, Default)]
+pub enum AccessMode {
+    /// Everyone, both lists are ignored
+    #[default]
+    Open,
+    /// Only addresses on the allowlist
+    Allowlist,
+    /// Everyone except addresses on the denylist
+    Denylist,
+}
+
+/// Access list used by one of the access modes, both are kept when the mode changes
+#[cw_serde]
+#[derive(Copy)]
+pub enum AccessListKind {
+    Allow,
+    Deny,
+}
+
+impl AccessListKind {
+    pub const ALL: [AccessListKind; 2] = [AccessListKind::Allow, AccessListKind::Deny];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccessListKind::Allow => "allow",
+            AccessListKind::Deny => "deny",
+        }
+    }
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
+
+#[cw_serde]
+#[derive(Copy)]
+pub enum Role {
+    /// Manages configuration and roles
+    Admin,
+    /// Allowed to reset any counter
+    Resetter,
+    /// Allowed to withdraw funds
+    Treasurer,
+    /// Allowed to pause and unpause operations during an incident
+    Guardian,
+}
+
+impl Role {
+    pub const ALL: [Role; 4] = [Role::Admin, Role::Resetter, Role::Treasurer, Role::Guardian];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::Resetter => "resetter",
+            Role::Treasurer => "treasurer",
+            Role::Guardian => "guardian",
+        }
+    }
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Operations which can be paused separately
+#[cw_serde]
+#[derive(Copy)]
+pub enum Operation {
+    /// Native and cw20 donations
+    Donations,
+    /// Every message sending funds out of the contract, refunds included
+    Withdrawals,
+    /// Counter resets
+    Resets,
+}
+
+impl Operation {
+    pub const ALL: [Operation; 3] = [
+        Operation::Donations,
+        Operation::Withdrawals,
+        Operation::Resets,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operation::Donations => "donations",
+            Operation::Withdrawals => "withdrawals",
+            Operation::Resets => "resets",
+        }
+    }
+}
+
+impl std::fmt::Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
     #[returns(ValueResp)]
-    Value {},
+    Value {
+        #[serde(default)]
+        counter_id: Option<String>,
+    },
+    /// Value of the counter once the block at `height` was executed
+    #[returns(ValueResp)]
+    ValueAt {
+        #[serde(default)]
+        counter_id: Option<String>,
+        height: u64,
+    },
+    #[returns(HistoryResp)]
+    History {
+        #[serde(default)]
+        counter_id: Option<String>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(CountersResp)]
+    Counters {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(ConfigResp)]
+    Config {},
+    #[returns(OwnerResp)]
+    Owner {},
+    #[returns(RolesResp)]
+    Roles {},
+    #[returns(HasRoleResp)]
+    HasRole { role: Role, address: String },
+    #[returns(DonorResp)]
+    Donor { address: String },
+    #[returns(DonorsResp)]
+    Donors {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(AcceptedDenomsResp)]
+    AcceptedDenoms {},
+    #[returns(Cw20TokensResp)]
+    Cw20Tokens {},
+    #[returns(CampaignResp)]
+    Campaign {},
+    #[returns(WithdrawalsResp)]
+    Withdrawals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(BeneficiariesResp)]
+    Beneficiaries {},
+    #[returns(FeeResp)]
+    Fee {},
+    #[returns(IbcChannelsResp)]
+    IbcChannels {},
+    #[returns(PendingWithdrawalResp)]
+    PendingWithdrawal { id: u64 },
+    #[returns(PendingWithdrawalsResp)]
+    PendingWithdrawals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(ProposalResp)]
+    Proposal { id: u64 },
+    #[returns(ProposalsResp)]
+    Proposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(VotesResp)]
+    Votes {
+        proposal_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Operations currently paused
+    #[returns(StatusResp)]
+    Status {},
+    #[returns(StatsResp)]
+    Stats {},
+    /// Donations in the order they were made
+    #[returns(DonationsResp)]
+    Donations {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(DonationsResp)]
+    DonationsBy {
+        donor: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(AccessListResp)]
+    AccessList {
+        list: AccessListKind,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
 pub enum ExecMsg {
-    Donate {},
+    Donate {
+        #[serde(default)]
+        counter_id: Option<String>,
+        #[serde(default)]
+        memo: Option<String>,
+    },
     Reset {
+        #[serde(default)]
+        counter_id: Option<String>,
         #[serde(default)]
         counter: u64,
     },
+    /// Creates a counter with its own minimal donation, owned by the sender if `owner` is not set
+    CreateCounter {
+        counter_id: String,
+        minimal_donation: Coin,
+        #[serde(default)]
+        owner: Option<String>,
+    },
+    /// Sends the whole balance to the sender, or creates a pending withdrawal if it exceeds the
+    /// limits of the approval policy
     Withdraw {},
+    /// Sends funds to `receiver`. If both `funds` and `cw20_funds` are empty, the whole native
+    /// balance and the balances of all accepted cw20 tokens are sent, otherwise only the listed
+    /// amounts, capped at the contract balance.
     WithdrawTo {
         receiver: String,
         #[serde(default)]
         funds: Vec<Coin>,
+        #[serde(default)]
+        cw20_funds: Vec<Cw20Coin>,
+    },
+    ProposeOwner {
+        new_owner: String,
+        #[serde(default)]
+        expiry: Option<Expiration>,
+    },
+    AcceptOwnership {},
+    CancelOwnershipTransfer {},
+    RenounceOwnership {},
+    GrantRole {
+        role: Role,
+        address: String,
+    },
+    RevokeRole {
+        role: Role,
+        address: String,
+    },
+    AddAcceptedDenom {
+        minimal_donation: Coin,
+    },
+    RemoveAcceptedDenom {
+        denom: String,
+    },
+    UpdateConfig(Box<ConfigUpdate>),
+    Receive(Cw20ReceiveMsg),
+    AddCw20Token {
+        token: String,
+        minimal_donation: Uint128,
+    },
+    RemoveCw20Token {
+        token: String,
+    },
+    /// Returns the contribution of the sender after a failed campaign
+    ClaimRefund {},
+    /// Replaces the beneficiaries, their weights have to sum up to `TOTAL_WEIGHT_BPS`
+    SetBeneficiaries {
+        beneficiaries: Vec<Beneficiary>,
+        /// Receives the rounding dust
+        remainder: String,
+    },
+    /// Splits the whole balance between the beneficiaries by weight
+    Distribute {},
+    /// Sends native funds to `remote_address` on another chain over an allowed ICS-20 channel.
+    /// The listed amounts are capped at the contract balance.
+    WithdrawIbc {
+        channel_id: String,
+        remote_address: String,
+        funds: Vec<Coin>,
+        /// Seconds after which the transfer is refunded, `IBC_DEFAULT_TIMEOUT` if not set
+        #[serde(default)]
+        timeout: Option<u64>,
+    },
+    AddIbcChannel {
+        channel_id: String,
+    },
+    RemoveIbcChannel {
+        channel_id: String,
+    },
+    ApproveWithdrawal {
+        id: u64,
+    },
+    RevokeApproval {
+        id: u64,
+    },
+    /// Sends a pending withdrawal approved by enough approvers, anyone can execute it
+    ExecuteWithdrawal {
+        id: u64,
+    },
+    /// Proposes sending `amount` to `receiver`, donors vote on it if governance is enabled
+    SpendProposal {
+        receiver: String,
+        amount: Vec<Coin>,
+        description: String,
+    },
+    Vote {
+        proposal_id: u64,
+        vote: VoteOption,
+    },
+    /// Sends the funds of a passed proposal once its voting period is over
+    ExecuteProposal {
+        proposal_id: u64,
+    },
+    /// Changes the fee, only allowed to the fee admin. Fields left empty are kept as they are.
+    UpdateFee {
+        fee_bps: Option<u64>,
+        fee_collector: Option<String>,
+        /// Hands the fee management over to another address
+        fee_admin: Option<String>,
+    },
+    /// Removes the memo of a donation, the donation itself stays in the log
+    HideMemo {
+        id: u64,
+    },
+    AddToAccessList {
+        list: AccessListKind,
+        addresses: Vec<String>,
+    },
+    RemoveFromAccessList {
+        list: AccessListKind,
+        addresses: Vec<String>,
+    },
+    /// Stops `operation` until it is unpaused, allowed to guardians
+    Pause {
+        operation: Operation,
+    },
+    Unpause {
+        operation: Operation,
+    },
+}
+
+/// Configuration changes, fields left empty are kept as they are. Optional settings are removed
+/// by setting them to `null`.
+#[cw_serde]
+#[derive(Default)]
+pub struct ConfigUpdate {
+    pub strict: Option<bool>,
+    pub refund: Option<RefundPolicy>,
+    /// Replaces all accepted denoms with their minimal donations
+    pub accepted_denoms: Option<Vec<Coin>>,
+    /// Replaces all accepted cw20 tokens with their minimal donations
+    pub cw20_tokens: Option<Vec<Cw20Coin>>,
+    /// Collection minting the milestone rewards, without one no rewards are minted
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "clearable"
+    )]
+    pub reward_collection: Option<Option<String>>,
+    pub milestones: Option<Milestones>,
+    /// Enables governance or changes its settings, open proposals keep theirs. Without governance
+    /// treasurers withdraw directly, proposals already created can still be voted on and executed.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "clearable"
+    )]
+    pub governance: Option<Option<Governance>>,
+    /// Replaces the approval policy, pending withdrawals count only approvals of current approvers.
+    /// Without a policy they can be executed without approvals.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "clearable"
+    )]
+    pub approval_policy: Option<Option<ApprovalPolicy>>,
+    /// Switches to another access list, the addresses of both lists are kept
+    pub access_mode: Option<AccessMode>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "clearable"
+    )]
+    pub rate_limit: Option<Option<RateLimit>>,
+    pub max_memo_length: Option<u32>,
+}
+
+/// Reads a present field as `Some`, so `null` can be told apart from a missing field
+fn clearable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::deserialize(deserializer).map(Some)
+}
+
+/// Messages accepted through `Cw20ReceiveMsg::msg`
+#[cw_serde]
+pub enum ReceiveMsg {
+    Donate {
+        #[serde(default)]
+        counter_id: Option<String>,
+        #[serde(default)]
+        memo: Option<String>,
     },
 }
 
@@ -35,3 +645,290 @@ pub enum ExecMsg {
 pub struct ValueResp {
     pub value: u64,
 }
+
+#[cw_serde]
+pub struct CounterResp {
+    pub counter_id: String,
+    pub value: u64,
+    /// `None` if the accepted denoms apply
+    pub minimal_donation: Option<Coin>,
+    pub owner: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct CountersResp {
+    pub counters: Vec<CounterResp>,
+}
+
+/// Reason of a counter value change
+#[cw_serde]
+pub enum ChangeCause {
+    Create,
+    Donate,
+    Reset,
+    Migrate,
+}
+
+#[cw_serde]
+pub struct HistoryEntry {
+    /// Sequence number of the change within its counter
+    pub id: u64,
+    pub height: u64,
+    pub value: u64,
+    pub cause: ChangeCause,
+    /// `None` for changes made by migrations
+    pub sender: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct HistoryResp {
+    pub entries: Vec<HistoryEntry>,
+}
+
+#[cw_serde]
+pub struct ConfigResp {
+    pub owner: Option<Addr>,
+    pub minimal_donation: Vec<Coin>,
+    pub cw20_tokens: Vec<Cw20CoinVerified>,
+    pub strict: bool,
+    pub refund: RefundPolicy,
+    pub reward_collection: Option<Addr>,
+    pub milestones: Milestones,
+    pub governance: Option<Governance>,
+    pub approval_policy: Option<ApprovalPolicy>,
+    pub access_mode: AccessMode,
+    pub rate_limit: Option<RateLimit>,
+    pub max_memo_length: u32,
+}
+
+#[cw_serde]
+pub struct OwnerResp {
+    pub owner: Option<Addr>,
+    pub pending_owner: Option<Addr>,
+    pub pending_expiry: Option<Expiration>,
+}
+
+#[cw_serde]
+pub struct RoleMembers {
+    pub role: Role,
+    pub members: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct RolesResp {
+    /// Holds every role without being listed in `roles`
+    pub owner: Option<Addr>,
+    pub roles: Vec<RoleMembers>,
+}
+
+#[cw_serde]
+pub struct HasRoleResp {
+    pub has_role: bool,
+}
+
+#[cw_serde]
+pub struct DonorResp {
+    pub address: Addr,
+    pub total: Vec<Coin>,
+    pub donations: u64,
+    pub first_donation_height: u64,
+    pub last_donation_height: u64,
+}
+
+#[cw_serde]
+pub struct DonorsResp {
+    pub donors: Vec<DonorResp>,
+}
+
+#[cw_serde]
+pub struct StatsResp {
+    pub counted_donations: u64,
+    pub uncounted_donations: u64,
+    pub unique_donors: u64,
+    /// Kept from donations per denom, cw20 tokens use the `cw20:<token>` denom
+    pub total_received: Vec<Coin>,
+    /// Paid out by successful withdrawals per denom, refunds are not included. IBC transfers are
+    /// counted when sent, even if they time out later.
+    pub total_withdrawn: Vec<Coin>,
+    pub last_donation_height: Option<u64>,
+    pub last_withdrawal_height: Option<u64>,
+}
+
+#[cw_serde]
+pub struct DonationResp {
+    pub id: u64,
+    pub donor: Addr,
+    pub counter_id: String,
+    /// Kept part of the donation, cw20 amounts use the ledger denom
+    pub amount: Vec<Coin>,
+    pub counted: bool,
+    pub height: u64,
+    pub memo: Option<String>,
+    pub memo_hidden: bool,
+}
+
+#[cw_serde]
+pub struct DonationsResp {
+    pub donations: Vec<DonationResp>,
+}
+
+#[cw_serde]
+pub struct AccessListResp {
+    pub mode: AccessMode,
+    pub addresses: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct AcceptedDenomsResp {
+    pub denoms: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct Cw20TokensResp {
+    pub tokens: Vec<Cw20CoinVerified>,
+}
+
+#[cw_serde]
+pub enum CampaignPhase {
+    NotStarted,
+    Open,
+    Succeeded,
+    Failed,
+}
+
+#[cw_serde]
+pub struct CampaignResp {
+    pub campaign: Option<Campaign>,
+    /// `None` if there is no campaign
+    pub phase: Option<CampaignPhase>,
+    pub raised: Vec<Coin>,
+}
+
+#[cw_serde]
+pub enum WithdrawalOutcome {
+    /// The payout was sent, its reply was not handled yet
+    Pending {},
+    Succeeded {},
+    Failed {
+        error: String,
+    },
+}
+
+#[cw_serde]
+pub struct WithdrawalResp {
+    pub id: u64,
+    pub receiver: Addr,
+    pub amount: Vec<Coin>,
+    pub height: u64,
+    pub outcome: WithdrawalOutcome,
+}
+
+#[cw_serde]
+pub struct WithdrawalsResp {
+    pub withdrawals: Vec<WithdrawalResp>,
+}
+
+#[cw_serde]
+pub struct BeneficiaryResp {
+    pub address: Addr,
+    pub weight_bps: u64,
+}
+
+#[cw_serde]
+pub struct BeneficiariesResp {
+    pub beneficiaries: Vec<BeneficiaryResp>,
+    /// `None` if no beneficiaries are set
+    pub remainder: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct FeeResp {
+    pub fee_bps: u64,
+    pub max_fee_bps: u64,
+    pub fee_collector: Option<Addr>,
+    pub fee_admin: Option<Addr>,
+    /// Fees forwarded so far per denom
+    pub collected: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct IbcChannelsResp {
+    pub channels: Vec<String>,
+}
+
+#[cw_serde]
+pub enum ProposalStatus {
+    Open,
+    /// Voting period is over and the proposal can be executed
+    Passed,
+    Rejected,
+    Executed,
+}
+
+#[cw_serde]
+pub struct ProposalResp {
+    pub id: u64,
+    pub proposer: Addr,
+    pub receiver: Addr,
+    pub amount: Vec<Coin>,
+    pub description: String,
+    pub expires: Expiration,
+    pub total_weight: Uint128,
+    pub yes: Uint128,
+    pub no: Uint128,
+    pub abstain: Uint128,
+    pub status: ProposalStatus,
+}
+
+#[cw_serde]
+pub struct ProposalsResp {
+    pub proposals: Vec<ProposalResp>,
+}
+
+#[cw_serde]
+pub struct VoteResp {
+    pub voter: Addr,
+    pub vote: VoteOption,
+    pub weight: Uint128,
+}
+
+#[cw_serde]
+pub struct VotesResp {
+    pub votes: Vec<VoteResp>,
+}
+
+#[cw_serde]
+pub enum PendingWithdrawalStatus {
+    Open,
+    Executed,
+    Expired,
+}
+
+#[cw_serde]
+pub struct PendingWithdrawalResp {
+    pub id: u64,
+    pub requester: Addr,
+    pub target: WithdrawalTarget,
+    pub amount: Vec<Coin>,
+    pub expires: Expiration,
+    pub approvals: Vec<Addr>,
+    pub status: PendingWithdrawalStatus,
+}
+
+#[cw_serde]
+pub struct PendingWithdrawalsResp {
+    pub withdrawals: Vec<PendingWithdrawalResp>,
+}
+
+#[cw_serde]
+pub struct PausedOperation {
+    pub operation: Operation,
+    pub paused_by: Addr,
+    /// Height of the block the operation was paused in
+    pub since: u64,
+}
+
+#[cw_serde]
+pub struct StatusResp {
+    pub paused: Vec<PausedOperation>,
+}
@@ -1,10 +1,32 @@
-use cosmwasm_std::{Addr, Coin, StdResult};
-use cw_multi_test::{App, ContractWrapper, Executor};
+use cosmwasm_std::{
+    to_json_binary, Addr, Coin, DepsMut, Env, Event, MessageInfo, Response, StdError, StdResult,
+    Uint128,
+};
+use cw20::{Cw20Coin, Cw20ExecuteMsg};
+use cw_multi_test::{App, AppResponse, ContractWrapper, Executor};
+use cw_utils::Expiration;
 
 use crate::error::ContractError;
-use crate::msg::{ExecMsg, InstantiateMsg, QueryMsg, ValueResp};
-use crate::{execute, instantiate, query};
+use crate::events::{
+    AddAcceptedDenomEvent, AddCw20TokenEvent, AddIbcChannelEvent, AddToAccessListEvent,
+    ApproveWithdrawalEvent, ConfigUpdateEvent, DistributeEvent, DonateEvent, ExecuteProposalEvent,
+    FeeUpdateEvent, GrantRoleEvent, HideMemoEvent, PauseEvent, RemoveAcceptedDenomEvent,
+    RemoveCw20TokenEvent, RemoveFromAccessListEvent, RemoveIbcChannelEvent, ResetEvent,
+    RevokeApprovalEvent, RevokeRoleEvent, SetBeneficiariesEvent, SpendProposalEvent, UnpauseEvent,
+    VoteEvent, WithdrawEvent, WithdrawIbcEvent, WithdrawToEvent, WithdrawalRequestEvent,
+};
+use crate::msg::{
+    AcceptedDenomsResp, AccessListKind, AccessListResp, BeneficiariesResp, Beneficiary,
+    CampaignResp, ConfigResp, ConfigUpdate, CountersResp, Cw20TokensResp, DonationsResp, DonorResp,
+    DonorsResp, ExecMsg, FeeResp, HasRoleResp, HistoryResp, IbcChannelsResp, InstantiateMsg,
+    MigrateMsg, Operation, OwnerResp, PendingWithdrawalResp, PendingWithdrawalsResp, ProposalResp,
+    ProposalsResp, QueryMsg, ReceiveMsg, Role, RolesResp, StatsResp, StatusResp, ValueResp,
+    VoteOption, VotesResp, WithdrawalsResp,
+};
+use crate::state::{LEGACY_COUNTER, LEGACY_MINIMAL_DONATION, OWNER};
+use crate::{execute, instantiate, migrate, query, reply};
 
+#[derive(Debug)]
 pub struct CountingContract(Addr);
 
 impl CountingContract {
@@ -13,46 +35,152 @@ impl CountingContract {
     }
 
     pub fn store_code(app: &mut App) -> u64 {
-        let contract = ContractWrapper::new(execute, instantiate, query);
+        let contract = ContractWrapper::new(execute, instantiate, query)
+            .with_migrate(migrate)
+            .with_reply(reply);
+        app.store_code(Box::new(contract))
+    }
+
+    /// Stores code instantiating the `0.1.0` state layout, without cw2 version info
+    pub fn store_code_v1(app: &mut App) -> u64 {
+        let contract = ContractWrapper::new(execute, instantiate_v1, query);
         app.store_code(Box::new(contract))
     }
 
     #[track_caller]
-    pub fn instantiate(
+    pub fn instantiate<'a>(
         app: &mut App,
         code_id: u64,
         sender: &Addr,
         label: &str,
         counter: impl Into<Option<u64>>,
         minimal_donation: Coin,
-    ) -> StdResult<Self> {
+        admin: impl Into<Option<&'a Addr>>,
+    ) -> Result<Self, ContractError> {
         let counter = counter.into().unwrap_or_default();
 
-        app.instantiate_contract(
+        Self::instantiate_with_msg(
+            app,
             code_id,
-            sender.clone(),
+            sender,
+            label,
             &InstantiateMsg {
                 counter,
                 minimal_donation,
+                ..Default::default()
             },
+            admin,
+        )
+    }
+
+    #[track_caller]
+    pub fn instantiate_with_msg<'a>(
+        app: &mut App,
+        code_id: u64,
+        sender: &Addr,
+        label: &str,
+        msg: &InstantiateMsg,
+        admin: impl Into<Option<&'a Addr>>,
+    ) -> Result<Self, ContractError> {
+        app.instantiate_contract(
+            code_id,
+            sender.clone(),
+            msg,
             &[],
             label,
-            None,
+            admin.into().map(Addr::to_string),
         )
         .map(CountingContract)
         .map_err(|err| err.downcast().unwrap())
     }
 
+    #[track_caller]
+    pub fn migrate(
+        self,
+        app: &mut App,
+        sender: &Addr,
+        code_id: u64,
+    ) -> Result<Self, ContractError> {
+        app.migrate_contract(sender.clone(), self.0.clone(), &MigrateMsg {}, code_id)
+            .map_err(|err| err.downcast().unwrap())
+            .map(|_| self)
+    }
+
     #[track_caller]
     pub fn donate(
         &self,
         app: &mut App,
         sender: &Addr,
         funds: &[Coin],
-    ) -> Result<(), ContractError> {
-        app.execute_contract(sender.clone(), self.0.clone(), &ExecMsg::Donate {}, funds)
-            .map_err(|err| err.downcast().unwrap())
-            .map(|_| ())
+    ) -> Result<DonateEvent, ContractError> {
+        self.donate_to(app, sender, None, funds)
+    }
+
+    #[track_caller]
+    pub fn donate_with_memo(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        funds: &[Coin],
+        memo: &str,
+    ) -> Result<DonateEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::Donate {
+                counter_id: None,
+                memo: Some(memo.to_owned()),
+            },
+            funds,
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn donate_to<'a>(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        counter_id: impl Into<Option<&'a str>>,
+        funds: &[Coin],
+    ) -> Result<DonateEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::Donate {
+                counter_id: counter_id.into().map(str::to_owned),
+                memo: None,
+            },
+            funds,
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn donate_cw20(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        token: &Addr,
+        amount: u128,
+    ) -> Result<DonateEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            token.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: self.0.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_json_binary(&ReceiveMsg::Donate {
+                    counter_id: None,
+                    memo: None,
+                })?,
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
     }
 
     #[track_caller]
@@ -61,12 +189,49 @@ impl CountingContract {
         app: &mut App,
         sender: &Addr,
         counter: impl Into<Option<u64>>,
-    ) -> Result<(), ContractError> {
+    ) -> Result<ResetEvent, ContractError> {
+        self.reset_counter(app, sender, None, counter)
+    }
+
+    #[track_caller]
+    pub fn reset_counter<'a>(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        counter_id: impl Into<Option<&'a str>>,
+        counter: impl Into<Option<u64>>,
+    ) -> Result<ResetEvent, ContractError> {
         let counter = counter.into().unwrap_or_default();
         app.execute_contract(
             sender.clone(),
             self.0.clone(),
-            &ExecMsg::Reset { counter },
+            &ExecMsg::Reset {
+                counter_id: counter_id.into().map(str::to_owned),
+                counter,
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn create_counter<'a>(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        counter_id: &str,
+        minimal_donation: Coin,
+        owner: impl Into<Option<&'a Addr>>,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::CreateCounter {
+                counter_id: counter_id.to_owned(),
+                minimal_donation,
+                owner: owner.into().map(Addr::to_string),
+            },
             &[],
         )
         .map_err(|err| err.downcast().unwrap())
@@ -74,10 +239,10 @@ impl CountingContract {
     }
 
     #[track_caller]
-    pub fn withdraw(&self, app: &mut App, sender: &Addr) -> Result<(), ContractError> {
+    pub fn withdraw(&self, app: &mut App, sender: &Addr) -> Result<WithdrawEvent, ContractError> {
         app.execute_contract(sender.clone(), self.0.clone(), &ExecMsg::Withdraw {}, &[])
             .map_err(|err| err.downcast().unwrap())
-            .map(|_| ())
+            .map(|resp| parse_event(&resp))
     }
 
     #[track_caller]
@@ -87,14 +252,38 @@ impl CountingContract {
         sender: &Addr,
         receiver: &Addr,
         funds: impl Into<Option<Vec<Coin>>>,
-    ) -> Result<(), ContractError> {
+        cw20_funds: impl Into<Option<Vec<Cw20Coin>>>,
+    ) -> Result<WithdrawToEvent, ContractError> {
         let funds = funds.into().unwrap_or_default();
+        let cw20_funds = cw20_funds.into().unwrap_or_default();
         app.execute_contract(
             sender.clone(),
             self.0.clone(),
             &ExecMsg::WithdrawTo {
                 receiver: receiver.to_string(),
                 funds,
+                cw20_funds,
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn propose_owner(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        new_owner: &Addr,
+        expiry: impl Into<Option<Expiration>>,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::ProposeOwner {
+                new_owner: new_owner.to_string(),
+                expiry: expiry.into(),
             },
             &[],
         )
@@ -103,14 +292,893 @@ impl CountingContract {
     }
 
     #[track_caller]
-    pub fn query_value(&self, app: &App) -> StdResult<ValueResp> {
-        app.wrap()
-            .query_wasm_smart(self.0.clone(), &QueryMsg::Value {})
+    pub fn accept_ownership(&self, app: &mut App, sender: &Addr) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::AcceptOwnership {},
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
     }
-}
 
-impl From<CountingContract> for Addr {
-    fn from(contract: CountingContract) -> Self {
-        contract.0
+    #[track_caller]
+    pub fn cancel_ownership_transfer(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::CancelOwnershipTransfer {},
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn renounce_ownership(&self, app: &mut App, sender: &Addr) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::RenounceOwnership {},
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn grant_role(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        role: Role,
+        address: &Addr,
+    ) -> Result<GrantRoleEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::GrantRole {
+                role,
+                address: address.to_string(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn revoke_role(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        role: Role,
+        address: &Addr,
+    ) -> Result<RevokeRoleEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::RevokeRole {
+                role,
+                address: address.to_string(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn add_accepted_denom(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        minimal_donation: Coin,
+    ) -> Result<AddAcceptedDenomEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::AddAcceptedDenom { minimal_donation },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn remove_accepted_denom(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        denom: &str,
+    ) -> Result<RemoveAcceptedDenomEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::RemoveAcceptedDenom {
+                denom: denom.to_owned(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
     }
+
+    #[track_caller]
+    pub fn update_config(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        update: ConfigUpdate,
+    ) -> Result<ConfigUpdateEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::UpdateConfig(Box::new(update)),
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn add_cw20_token(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        token: &Addr,
+        minimal_donation: u128,
+    ) -> Result<AddCw20TokenEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::AddCw20Token {
+                token: token.to_string(),
+                minimal_donation: Uint128::new(minimal_donation),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn remove_cw20_token(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        token: &Addr,
+    ) -> Result<RemoveCw20TokenEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::RemoveCw20Token {
+                token: token.to_string(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn claim_refund(&self, app: &mut App, sender: &Addr) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::ClaimRefund {},
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn withdraw_ibc(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        channel_id: &str,
+        remote_address: &str,
+        funds: &[Coin],
+    ) -> Result<WithdrawIbcEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::WithdrawIbc {
+                channel_id: channel_id.to_owned(),
+                remote_address: remote_address.to_owned(),
+                funds: funds.to_vec(),
+                timeout: None,
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn add_ibc_channel(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        channel_id: &str,
+    ) -> Result<AddIbcChannelEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::AddIbcChannel {
+                channel_id: channel_id.to_owned(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn remove_ibc_channel(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        channel_id: &str,
+    ) -> Result<RemoveIbcChannelEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::RemoveIbcChannel {
+                channel_id: channel_id.to_owned(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn spend_proposal(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        receiver: &Addr,
+        amount: &[Coin],
+        description: &str,
+    ) -> Result<SpendProposalEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::SpendProposal {
+                receiver: receiver.to_string(),
+                amount: amount.to_vec(),
+                description: description.to_owned(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn vote(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        proposal_id: u64,
+        vote: VoteOption,
+    ) -> Result<VoteEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::Vote { proposal_id, vote },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn execute_proposal(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        proposal_id: u64,
+    ) -> Result<ExecuteProposalEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::ExecuteProposal { proposal_id },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn request_withdrawal(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        receiver: &Addr,
+        funds: impl Into<Option<Vec<Coin>>>,
+    ) -> Result<WithdrawalRequestEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::WithdrawTo {
+                receiver: receiver.to_string(),
+                funds: funds.into().unwrap_or_default(),
+                cw20_funds: vec![],
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn request_distribution(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+    ) -> Result<WithdrawalRequestEvent, ContractError> {
+        app.execute_contract(sender.clone(), self.0.clone(), &ExecMsg::Distribute {}, &[])
+            .map_err(|err| err.downcast().unwrap())
+            .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn approve_withdrawal(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        id: u64,
+    ) -> Result<ApproveWithdrawalEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::ApproveWithdrawal { id },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn revoke_approval(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        id: u64,
+    ) -> Result<RevokeApprovalEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::RevokeApproval { id },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn execute_withdrawal(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        id: u64,
+    ) -> Result<WithdrawToEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::ExecuteWithdrawal { id },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn execute_distribution(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        id: u64,
+    ) -> Result<DistributeEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::ExecuteWithdrawal { id },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn hide_memo(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        id: u64,
+    ) -> Result<HideMemoEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::HideMemo { id },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn add_to_access_list(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        list: AccessListKind,
+        addresses: &[&Addr],
+    ) -> Result<AddToAccessListEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::AddToAccessList {
+                list,
+                addresses: addresses.iter().map(ToString::to_string).collect(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn remove_from_access_list(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        list: AccessListKind,
+        addresses: &[&Addr],
+    ) -> Result<RemoveFromAccessListEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::RemoveFromAccessList {
+                list,
+                addresses: addresses.iter().map(ToString::to_string).collect(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn pause(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        operation: Operation,
+    ) -> Result<PauseEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::Pause { operation },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn unpause(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        operation: Operation,
+    ) -> Result<UnpauseEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::Unpause { operation },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn set_beneficiaries(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        beneficiaries: &[(&Addr, u64)],
+        remainder: &Addr,
+    ) -> Result<SetBeneficiariesEvent, ContractError> {
+        let beneficiaries = beneficiaries
+            .iter()
+            .map(|(address, weight_bps)| Beneficiary {
+                address: address.to_string(),
+                weight_bps: *weight_bps,
+            })
+            .collect();
+
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::SetBeneficiaries {
+                beneficiaries,
+                remainder: remainder.to_string(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn update_fee<'a>(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        fee_bps: impl Into<Option<u64>>,
+        fee_collector: impl Into<Option<&'a Addr>>,
+        fee_admin: impl Into<Option<&'a Addr>>,
+    ) -> Result<FeeUpdateEvent, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecMsg::UpdateFee {
+                fee_bps: fee_bps.into(),
+                fee_collector: fee_collector.into().map(Addr::to_string),
+                fee_admin: fee_admin.into().map(Addr::to_string),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn distribute(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+    ) -> Result<DistributeEvent, ContractError> {
+        app.execute_contract(sender.clone(), self.0.clone(), &ExecMsg::Distribute {}, &[])
+            .map_err(|err| err.downcast().unwrap())
+            .map(|resp| parse_event(&resp))
+    }
+
+    #[track_caller]
+    pub fn query_value(&self, app: &App) -> StdResult<ValueResp> {
+        self.query_counter_value(app, None)
+    }
+
+    #[track_caller]
+    pub fn query_counter_value<'a>(
+        &self,
+        app: &App,
+        counter_id: impl Into<Option<&'a str>>,
+    ) -> StdResult<ValueResp> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::Value {
+                counter_id: counter_id.into().map(str::to_owned),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_value_at(&self, app: &App, height: u64) -> StdResult<ValueResp> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::ValueAt {
+                counter_id: None,
+                height,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_history<'a>(
+        &self,
+        app: &App,
+        counter_id: impl Into<Option<&'a str>>,
+        start_after: impl Into<Option<u64>>,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<HistoryResp> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::History {
+                counter_id: counter_id.into().map(str::to_owned),
+                start_after: start_after.into(),
+                limit: limit.into(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_counters<'a>(
+        &self,
+        app: &App,
+        start_after: impl Into<Option<&'a str>>,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<CountersResp> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::Counters {
+                start_after: start_after.into().map(str::to_owned),
+                limit: limit.into(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_config(&self, app: &App) -> StdResult<ConfigResp> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Config {})
+    }
+
+    #[track_caller]
+    pub fn query_owner(&self, app: &App) -> StdResult<OwnerResp> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Owner {})
+    }
+
+    #[track_caller]
+    pub fn query_roles(&self, app: &App) -> StdResult<RolesResp> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Roles {})
+    }
+
+    #[track_caller]
+    pub fn query_has_role(&self, app: &App, role: Role, address: &Addr) -> StdResult<HasRoleResp> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::HasRole {
+                role,
+                address: address.to_string(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_donor(&self, app: &App, address: &Addr) -> StdResult<DonorResp> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::Donor {
+                address: address.to_string(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_donors<'a>(
+        &self,
+        app: &App,
+        start_after: impl Into<Option<&'a Addr>>,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<DonorsResp> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::Donors {
+                start_after: start_after.into().map(Addr::to_string),
+                limit: limit.into(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_accepted_denoms(&self, app: &App) -> StdResult<AcceptedDenomsResp> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::AcceptedDenoms {})
+    }
+
+    #[track_caller]
+    pub fn query_cw20_tokens(&self, app: &App) -> StdResult<Cw20TokensResp> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Cw20Tokens {})
+    }
+
+    #[track_caller]
+    pub fn query_withdrawals(
+        &self,
+        app: &App,
+        start_after: impl Into<Option<u64>>,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<WithdrawalsResp> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::Withdrawals {
+                start_after: start_after.into(),
+                limit: limit.into(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_ibc_channels(&self, app: &App) -> StdResult<IbcChannelsResp> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::IbcChannels {})
+    }
+
+    #[track_caller]
+    pub fn query_pending_withdrawal(&self, app: &App, id: u64) -> StdResult<PendingWithdrawalResp> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::PendingWithdrawal { id })
+    }
+
+    #[track_caller]
+    pub fn query_pending_withdrawals(
+        &self,
+        app: &App,
+        start_after: impl Into<Option<u64>>,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<PendingWithdrawalsResp> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::PendingWithdrawals {
+                start_after: start_after.into(),
+                limit: limit.into(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_donations(
+        &self,
+        app: &App,
+        start_after: impl Into<Option<u64>>,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<DonationsResp> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::Donations {
+                start_after: start_after.into(),
+                limit: limit.into(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_donations_by(
+        &self,
+        app: &App,
+        donor: &Addr,
+        start_after: impl Into<Option<u64>>,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<DonationsResp> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::DonationsBy {
+                donor: donor.to_string(),
+                start_after: start_after.into(),
+                limit: limit.into(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_access_list<'a>(
+        &self,
+        app: &App,
+        list: AccessListKind,
+        start_after: impl Into<Option<&'a Addr>>,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<AccessListResp> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::AccessList {
+                list,
+                start_after: start_after.into().map(Addr::to_string),
+                limit: limit.into(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_stats(&self, app: &App) -> StdResult<StatsResp> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Stats {})
+    }
+
+    #[track_caller]
+    pub fn query_status(&self, app: &App) -> StdResult<StatusResp> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Status {})
+    }
+
+    #[track_caller]
+    pub fn query_proposal(&self, app: &App, id: u64) -> StdResult<ProposalResp> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Proposal { id })
+    }
+
+    #[track_caller]
+    pub fn query_proposals(
+        &self,
+        app: &App,
+        start_after: impl Into<Option<u64>>,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<ProposalsResp> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::Proposals {
+                start_after: start_after.into(),
+                limit: limit.into(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_votes<'a>(
+        &self,
+        app: &App,
+        proposal_id: u64,
+        start_after: impl Into<Option<&'a Addr>>,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<VotesResp> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::Votes {
+                proposal_id,
+                start_after: start_after.into().map(Addr::to_string),
+                limit: limit.into(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_fee(&self, app: &App) -> StdResult<FeeResp> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Fee {})
+    }
+
+    #[track_caller]
+    pub fn query_beneficiaries(&self, app: &App) -> StdResult<BeneficiariesResp> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Beneficiaries {})
+    }
+
+    #[track_caller]
+    pub fn query_campaign(&self, app: &App) -> StdResult<CampaignResp> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Campaign {})
+    }
+}
+
+/// Finds the event of type `T` emitted by the execution
+#[track_caller]
+fn parse_event<T>(resp: &AppResponse) -> T
+where
+    T: for<'a> TryFrom<&'a Event, Error = StdError>,
+{
+    resp.events
+        .iter()
+        .find_map(|event| T::try_from(event).ok())
+        .expect("event not emitted")
+}
+
+impl From<CountingContract> for Addr {
+    fn from(contract: CountingContract) -> Self {
+        contract.0
+    }
+}
+
+fn instantiate_v1(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    LEGACY_COUNTER.save(deps.storage, &msg.counter)?;
+    LEGACY_MINIMAL_DONATION.save(deps.storage, &msg.minimal_donation)?;
+    OWNER.save(deps.storage, &info.sender)?;
+    Ok(Response::new())
 }
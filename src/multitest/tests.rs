@@ -1,12 +1,81 @@
-use cosmwasm_std::{coin, coins, Addr};
-use cw_multi_test::App;
+use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+use cosmwasm_std::{
+    coin, coins, from_json, to_json_binary, to_json_vec, Addr, Binary, Coin, Decimal, Deps,
+    DepsMut, Empty, Env, Event, IbcMsg, IbcTimeout, MessageInfo, Response, StdError, StdResult,
+    SubMsg, Uint128,
+};
+use cw2::ContractVersion;
+use cw20::{BalanceResponse, Cw20Coin, Cw20CoinVerified, Cw20ExecuteMsg, Cw20QueryMsg};
+use cw721::{AllNftInfoResponse, NumTokensResponse};
+use cw_multi_test::{App, ContractWrapper, Executor};
+use cw_utils::{Duration, Expiration, Scheduled};
 
+use crate::contract::{CONTRACT_NAME, CONTRACT_VERSION};
 use crate::error::ContractError;
-use crate::msg::ValueResp;
+use crate::events::{
+    ApproveWithdrawalEvent, ConfigChange, ConfigUpdateEvent, DistributeEvent, DonateEvent,
+    ExecuteProposalEvent, FeeUpdateEvent, GrantRoleEvent, PauseEvent, ResetEvent, RevokeRoleEvent,
+    SetBeneficiariesEvent, WithdrawEvent, WithdrawIbcEvent, WithdrawToEvent,
+    WithdrawalRequestEvent,
+};
+use crate::msg::{
+    AcceptedDenomsResp, AccessListKind, AccessListResp, AccessMode, ApprovalPolicy,
+    BeneficiariesResp, BeneficiaryResp, Campaign, CampaignPhase, CampaignResp, ChangeCause,
+    ConfigResp, ConfigUpdate, CounterResp, Cw20TokensResp, DonationCap, DonationResp, DonorResp,
+    ExecMsg, FeeResp, Governance, HasRoleResp, HistoryEntry, IbcChannelsResp, InstantiateMsg,
+    Milestones, Operation, OwnerResp, PausedOperation, PendingWithdrawalResp,
+    PendingWithdrawalStatus, ProposalResp, ProposalStatus, QueryMsg, RateLimit, RateLimitAction,
+    RateWindow, RefundPolicy, RewardMetadata, Role, RoleMembers, RolesResp, StatsResp, StatusResp,
+    ValueResp, VoteOption, VoteResp, VotesResp, WithdrawalOutcome, WithdrawalResp,
+    WithdrawalTarget, DEFAULT_COUNTER, DEFAULT_MAX_MEMO_LENGTH,
+};
+use crate::{execute, instantiate, query};
 
 use super::contract::CountingContract;
 
 const ATOM: &str = "atom";
+const OSMO: &str = "osmo";
+
+fn instantiate_cw20(app: &mut App, holder: &Addr, amount: u128) -> Addr {
+    let code_id = app.store_code(Box::new(ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    )));
+
+    app.instantiate_contract(
+        code_id,
+        holder.clone(),
+        &cw20_base::msg::InstantiateMsg {
+            name: "Test token".to_owned(),
+            symbol: "TEST".to_owned(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: holder.to_string(),
+                amount: Uint128::new(amount),
+            }],
+            mint: None,
+            marketing: None,
+        },
+        &[],
+        "Test token",
+        None,
+    )
+    .unwrap()
+}
+
+fn cw20_balance(app: &App, token: &Addr, address: &Addr) -> u128 {
+    let resp: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token,
+            &Cw20QueryMsg::Balance {
+                address: address.to_string(),
+            },
+        )
+        .unwrap();
+    resp.balance.u128()
+}
 
 #[test]
 fn query_value() {
@@ -22,6 +91,7 @@ fn query_value() {
         "Counting contract",
         10,
         coin(10, ATOM),
+        None,
     )
     .unwrap();
 
@@ -44,6 +114,7 @@ fn donate() {
         "Counting contract",
         0,
         coin(10, ATOM),
+        None,
     )
     .unwrap();
 
@@ -72,6 +143,7 @@ fn donate_with_funds() {
         "Counting contract",
         None,
         coin(10, ATOM),
+        None,
     )
     .unwrap();
 
@@ -97,6 +169,7 @@ fn expecting_no_funds() {
         "Counting contract",
         None,
         coin(0, ATOM),
+        None,
     )
     .unwrap();
 
@@ -120,6 +193,7 @@ fn reset() {
         "Counting contract",
         None,
         coin(10, ATOM),
+        None,
     )
     .unwrap();
 
@@ -149,6 +223,7 @@ fn withdraw() {
         "Counting contract",
         None,
         coin(10, ATOM),
+        None,
     )
     .unwrap();
 
@@ -190,6 +265,7 @@ fn withdraw_to() {
         "Counting contract",
         None,
         coin(10, ATOM),
+        None,
     )
     .unwrap();
 
@@ -198,7 +274,7 @@ fn withdraw_to() {
         .unwrap();
 
     contract
-        .withdraw_to(&mut app, &owner, &receiver, coins(5, ATOM))
+        .withdraw_to(&mut app, &owner, &receiver, coins(5, ATOM), None)
         .unwrap();
 
     assert_eq!(app.wrap().query_all_balances(owner).unwrap(), vec![]);
@@ -228,14 +304,15 @@ fn unauthorized_withdraw() {
         "Counting contract",
         None,
         coin(10, ATOM),
+        None,
     )
     .unwrap();
 
     let err = contract.withdraw(&mut app, &member).unwrap_err();
     assert_eq!(
         err,
-        ContractError::Unauthorized {
-            owner: owner.into()
+        ContractError::MissingRole {
+            role: Role::Treasurer
         },
     );
 }
@@ -255,16 +332,17 @@ fn unauthorized_withdraw_to() {
         "Counting contract",
         None,
         coin(10, ATOM),
+        None,
     )
     .unwrap();
 
     let err = contract
-        .withdraw_to(&mut app, &member, &owner, vec![])
+        .withdraw_to(&mut app, &member, &owner, vec![], None)
         .unwrap_err();
     assert_eq!(
         err,
-        ContractError::Unauthorized {
-            owner: owner.into()
+        ContractError::MissingRole {
+            role: Role::Treasurer
         },
     );
 }
@@ -284,15 +362,4900 @@ fn unauthorized_reset() {
         "Counting contract",
         None,
         coin(10, ATOM),
+        None,
     )
     .unwrap();
 
     let err = contract.reset(&mut app, &member, 10).unwrap_err();
     assert_eq!(
         err,
-        ContractError::Unauthorized {
-            owner: owner.into()
+        ContractError::MissingRole {
+            role: Role::Resetter
+        },
+    );
+}
+
+#[test]
+fn migrate_from_v1() {
+    let owner = Addr::unchecked("owner");
+    let admin = Addr::unchecked("admin");
+
+    let mut app = App::default();
+
+    let old_code_id = CountingContract::store_code_v1(&mut app);
+    let new_code_id = CountingContract::store_code(&mut app);
+
+    let contract = CountingContract::instantiate(
+        &mut app,
+        old_code_id,
+        &owner,
+        "Counting contract",
+        10,
+        coin(10, ATOM),
+        &admin,
+    )
+    .unwrap();
+
+    let contract = contract.migrate(&mut app, &admin, new_code_id).unwrap();
+
+    let resp = contract.query_value(&app).unwrap();
+    assert_eq!(resp, ValueResp { value: 10 });
+
+    let version = cw2::query_contract_info(&app.wrap(), contract.addr()).unwrap();
+    assert_eq!(
+        version,
+        ContractVersion {
+            contract: CONTRACT_NAME.to_owned(),
+            version: CONTRACT_VERSION.to_owned(),
+        }
+    );
+
+    for role in Role::ALL {
+        let resp = contract.query_has_role(&app, role, &owner).unwrap();
+        assert!(resp.has_role);
+    }
+
+    let resp = contract.query_accepted_denoms(&app).unwrap();
+    assert_eq!(
+        resp,
+        AcceptedDenomsResp {
+            denoms: coins(10, ATOM)
+        }
+    );
+
+    contract.reset(&mut app, &owner, 20).unwrap();
+
+    let resp = contract.query_value(&app).unwrap();
+    assert_eq!(resp, ValueResp { value: 20 });
+
+    let resp = contract.query_history(&app, None, None, None).unwrap();
+    let causes: Vec<_> = resp.entries.into_iter().map(|e| e.cause).collect();
+    assert_eq!(causes, [ChangeCause::Migrate, ChangeCause::Reset]);
+}
+
+#[test]
+fn migrate_same_version() {
+    let owner = Addr::unchecked("owner");
+    let admin = Addr::unchecked("admin");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        10,
+        coin(10, ATOM),
+        &admin,
+    )
+    .unwrap();
+
+    let contract = contract.migrate(&mut app, &admin, code_id).unwrap();
+
+    let resp = contract.query_value(&app).unwrap();
+    assert_eq!(resp, ValueResp { value: 10 });
+}
+
+#[test]
+fn migrate_from_newer_version() {
+    let owner = Addr::unchecked("owner");
+    let admin = Addr::unchecked("admin");
+
+    let mut app = App::default();
+
+    let old_code_id = app.store_code(Box::new(ContractWrapper::new(
+        execute,
+        |mut deps: DepsMut, env: Env, info: MessageInfo, msg: InstantiateMsg| {
+            let resp = instantiate(deps.branch(), env, info, msg)?;
+            cw2::set_contract_version(deps.storage, CONTRACT_NAME, "99.0.0")?;
+            Ok::<_, ContractError>(resp)
+        },
+        query,
+    )));
+    let new_code_id = CountingContract::store_code(&mut app);
+
+    let contract = CountingContract::instantiate(
+        &mut app,
+        old_code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        &admin,
+    )
+    .unwrap();
+
+    let err = contract.migrate(&mut app, &admin, new_code_id).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::CannotDowngrade {
+            current: "99.0.0".to_owned(),
+            new: CONTRACT_VERSION.to_owned(),
+        }
+    );
+}
+
+#[test]
+fn migrate_from_other_contract() {
+    let owner = Addr::unchecked("owner");
+    let admin = Addr::unchecked("admin");
+
+    let mut app = App::default();
+
+    let old_code_id = app.store_code(Box::new(ContractWrapper::new(
+        execute,
+        |mut deps: DepsMut, env: Env, info: MessageInfo, msg: InstantiateMsg| {
+            let resp = instantiate(deps.branch(), env, info, msg)?;
+            cw2::set_contract_version(deps.storage, "other_contract", CONTRACT_VERSION)?;
+            Ok::<_, ContractError>(resp)
         },
+        query,
+    )));
+    let new_code_id = CountingContract::store_code(&mut app);
+
+    let contract = CountingContract::instantiate(
+        &mut app,
+        old_code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        &admin,
+    )
+    .unwrap();
+
+    let err = contract.migrate(&mut app, &admin, new_code_id).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidContractName {
+            expected: CONTRACT_NAME.to_owned(),
+            actual: "other_contract".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn transfer_ownership() {
+    let owner = Addr::unchecked("owner");
+    let new_owner = Addr::unchecked("new_owner");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    contract
+        .propose_owner(&mut app, &owner, &new_owner, None)
+        .unwrap();
+
+    let resp = contract.query_owner(&app).unwrap();
+    assert_eq!(
+        resp,
+        OwnerResp {
+            owner: Some(owner.clone()),
+            pending_owner: Some(new_owner.clone()),
+            pending_expiry: Some(Expiration::Never {}),
+        }
+    );
+
+    let err = contract.accept_ownership(&mut app, &owner).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NotPendingOwner {
+            pending: new_owner.to_string()
+        }
+    );
+
+    contract.accept_ownership(&mut app, &new_owner).unwrap();
+
+    let resp = contract.query_owner(&app).unwrap();
+    assert_eq!(
+        resp,
+        OwnerResp {
+            owner: Some(new_owner.clone()),
+            pending_owner: None,
+            pending_expiry: None,
+        }
+    );
+
+    let err = contract
+        .propose_owner(&mut app, &owner, &owner, None)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: new_owner.to_string()
+        }
+    );
+
+    assert_lost_roles(&mut app, &contract, &owner);
+
+    contract.reset(&mut app, &new_owner, 10).unwrap();
+}
+
+#[test]
+fn expired_ownership_transfer() {
+    let owner = Addr::unchecked("owner");
+    let new_owner = Addr::unchecked("new_owner");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    let expiry = Expiration::AtHeight(app.block_info().height + 10);
+    contract
+        .propose_owner(&mut app, &owner, &new_owner, expiry)
+        .unwrap();
+
+    app.update_block(|block| block.height += 10);
+
+    let err = contract.accept_ownership(&mut app, &new_owner).unwrap_err();
+    assert_eq!(err, ContractError::TransferExpired {});
+
+    let resp = contract.query_owner(&app).unwrap();
+    assert_eq!(resp.owner, Some(owner));
+}
+
+#[test]
+fn cancel_ownership_transfer() {
+    let owner = Addr::unchecked("owner");
+    let new_owner = Addr::unchecked("new_owner");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    contract
+        .propose_owner(&mut app, &owner, &new_owner, None)
+        .unwrap();
+
+    let err = contract
+        .cancel_ownership_transfer(&mut app, &new_owner)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: owner.to_string()
+        }
+    );
+
+    contract
+        .cancel_ownership_transfer(&mut app, &owner)
+        .unwrap();
+
+    let err = contract.accept_ownership(&mut app, &new_owner).unwrap_err();
+    assert_eq!(err, ContractError::NoPendingOwner {});
+}
+
+#[test]
+fn renounce_ownership() {
+    let owner = Addr::unchecked("owner");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    contract.renounce_ownership(&mut app, &owner).unwrap();
+
+    let resp = contract.query_owner(&app).unwrap();
+    assert_eq!(
+        resp,
+        OwnerResp {
+            owner: None,
+            pending_owner: None,
+            pending_expiry: None,
+        }
+    );
+    let resp = contract.query_roles(&app).unwrap();
+    assert_eq!(resp.owner, None);
+
+    let err = contract
+        .propose_owner(&mut app, &owner, &owner, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::OwnershipRenounced {});
+
+    assert_lost_roles(&mut app, &contract, &owner);
+}
+
+/// Checks a previous owner can't use any of the roles it held as the owner
+#[track_caller]
+fn assert_lost_roles(app: &mut App, contract: &CountingContract, previous_owner: &Addr) {
+    let err = contract.reset(app, previous_owner, 10).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MissingRole {
+            role: Role::Resetter
+        }
+    );
+
+    let err = contract.withdraw(app, previous_owner).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MissingRole {
+            role: Role::Treasurer
+        }
+    );
+
+    let err = contract
+        .grant_role(app, previous_owner, Role::Treasurer, previous_owner)
+        .unwrap_err();
+    assert_eq!(err, ContractError::MissingRole { role: Role::Admin });
+}
+
+#[test]
+fn instantiator_has_all_roles() {
+    let owner = Addr::unchecked("owner");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    for role in Role::ALL {
+        let resp = contract.query_has_role(&app, role, &owner).unwrap();
+        assert!(resp.has_role);
+    }
+
+    // The owner holds its roles implicitly, it is reported apart from the listed members
+    let resp = contract.query_roles(&app).unwrap();
+    assert_eq!(
+        resp,
+        RolesResp {
+            owner: Some(owner.clone()),
+            roles: Role::ALL
+                .into_iter()
+                .map(|role| RoleMembers {
+                    role,
+                    members: vec![],
+                })
+                .collect(),
+        }
+    );
+}
+
+#[test]
+fn grant_and_revoke_role() {
+    let owner = Addr::unchecked("owner");
+    let resetter = Addr::unchecked("resetter");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    let event = contract
+        .grant_role(&mut app, &owner, Role::Resetter, &resetter)
+        .unwrap();
+    assert_eq!(
+        event,
+        GrantRoleEvent {
+            sender: owner.clone(),
+            role: Role::Resetter,
+            address: resetter.clone(),
+        }
+    );
+
+    let resp = contract
+        .query_has_role(&app, Role::Resetter, &resetter)
+        .unwrap();
+    assert_eq!(resp, HasRoleResp { has_role: true });
+
+    contract.reset(&mut app, &resetter, 5).unwrap();
+    let resp = contract.query_value(&app).unwrap();
+    assert_eq!(resp, ValueResp { value: 5 });
+
+    let err = contract.withdraw(&mut app, &resetter).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MissingRole {
+            role: Role::Treasurer
+        }
+    );
+
+    let event = contract
+        .revoke_role(&mut app, &owner, Role::Resetter, &resetter)
+        .unwrap();
+    assert_eq!(
+        event,
+        RevokeRoleEvent {
+            sender: owner.clone(),
+            role: Role::Resetter,
+            address: resetter.clone(),
+        }
+    );
+
+    let resp = contract
+        .query_has_role(&app, Role::Resetter, &resetter)
+        .unwrap();
+    assert_eq!(resp, HasRoleResp { has_role: false });
+
+    let err = contract.reset(&mut app, &resetter, 10).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MissingRole {
+            role: Role::Resetter
+        }
+    );
+}
+
+#[test]
+fn unauthorized_grant_role() {
+    let owner = Addr::unchecked("owner");
+    let member = Addr::unchecked("member");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    let err = contract
+        .grant_role(&mut app, &member, Role::Treasurer, &member)
+        .unwrap_err();
+    assert_eq!(err, ContractError::MissingRole { role: Role::Admin });
+}
+
+#[test]
+fn donor_ledger() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, vec![coin(30, ATOM), coin(5, OSMO)])
+            .unwrap();
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    let first_height = app.block_info().height;
+    contract
+        .donate(&mut app, &sender, &coins(20, ATOM))
+        .unwrap();
+
+    app.update_block(|block| block.height += 5);
+    contract
+        .donate(&mut app, &sender, &[coin(5, ATOM), coin(5, OSMO)])
+        .unwrap();
+
+    let resp = contract.query_donor(&app, &sender).unwrap();
+    assert_eq!(
+        resp,
+        DonorResp {
+            address: sender.clone(),
+            total: vec![coin(25, ATOM), coin(5, OSMO)],
+            donations: 1,
+            first_donation_height: first_height,
+            last_donation_height: first_height + 5,
+        }
+    );
+
+    contract.query_donor(&app, &owner).unwrap_err();
+}
+
+#[test]
+fn donors_pagination() {
+    let owner = Addr::unchecked("owner");
+    let donors = [
+        Addr::unchecked("donor1"),
+        Addr::unchecked("donor2"),
+        Addr::unchecked("donor3"),
+    ];
+
+    let mut app = App::new(|router, _api, storage| {
+        for donor in &donors {
+            router
+                .bank
+                .init_balance(storage, donor, coins(10, ATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    for donor in &donors {
+        contract.donate(&mut app, donor, &coins(10, ATOM)).unwrap();
+    }
+
+    let resp = contract.query_donors(&app, None, 2).unwrap();
+    let addresses: Vec<_> = resp.donors.iter().map(|d| d.address.clone()).collect();
+    assert_eq!(addresses, donors[..2]);
+
+    let resp = contract.query_donors(&app, &donors[1], None).unwrap();
+    let addresses: Vec<_> = resp.donors.iter().map(|d| d.address.clone()).collect();
+    assert_eq!(addresses, donors[2..]);
+}
+
+#[test]
+fn donate_multiple_denoms() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, vec![coin(10, ATOM), coin(10, OSMO)])
+            .unwrap();
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            counter: 0,
+            minimal_donation: coin(10, ATOM),
+            accepted_denoms: vec![coin(5, OSMO)],
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+
+    contract
+        .donate(&mut app, &sender, &[coin(5, ATOM), coin(4, OSMO)])
+        .unwrap();
+    let resp = contract.query_value(&app).unwrap();
+    assert_eq!(resp, ValueResp { value: 0 });
+
+    contract.donate(&mut app, &sender, &coins(5, OSMO)).unwrap();
+    let resp = contract.query_value(&app).unwrap();
+    assert_eq!(resp, ValueResp { value: 1 });
+}
+
+#[test]
+fn duplicate_accepted_denom() {
+    let owner = Addr::unchecked("owner");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let err = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            counter: 0,
+            minimal_donation: coin(10, ATOM),
+            accepted_denoms: vec![coin(5, ATOM)],
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        err,
+        ContractError::DuplicateDenom {
+            denom: ATOM.to_owned()
+        }
+    );
+}
+
+#[test]
+fn manage_accepted_denoms() {
+    let owner = Addr::unchecked("owner");
+    let member = Addr::unchecked("member");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    let err = contract
+        .add_accepted_denom(&mut app, &member, coin(5, OSMO))
+        .unwrap_err();
+    assert_eq!(err, ContractError::MissingRole { role: Role::Admin });
+
+    contract
+        .add_accepted_denom(&mut app, &owner, coin(5, OSMO))
+        .unwrap();
+
+    let resp = contract.query_accepted_denoms(&app).unwrap();
+    assert_eq!(
+        resp,
+        AcceptedDenomsResp {
+            denoms: vec![coin(10, ATOM), coin(5, OSMO)]
+        }
+    );
+
+    contract
+        .remove_accepted_denom(&mut app, &owner, ATOM)
+        .unwrap();
+
+    let err = contract
+        .remove_accepted_denom(&mut app, &owner, ATOM)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::UnsupportedDenom {
+            denom: ATOM.to_owned()
+        }
+    );
+
+    let resp = contract.query_accepted_denoms(&app).unwrap();
+    assert_eq!(
+        resp,
+        AcceptedDenomsResp {
+            denoms: coins(5, OSMO)
+        }
+    );
+}
+
+#[test]
+fn donate_cw20() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::default();
+
+    let token = instantiate_cw20(&mut app, &sender, 20);
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    contract
+        .add_cw20_token(&mut app, &owner, &token, 10)
+        .unwrap();
+
+    contract.donate_cw20(&mut app, &sender, &token, 5).unwrap();
+    let resp = contract.query_value(&app).unwrap();
+    assert_eq!(resp, ValueResp { value: 0 });
+
+    contract.donate_cw20(&mut app, &sender, &token, 10).unwrap();
+    let resp = contract.query_value(&app).unwrap();
+    assert_eq!(resp, ValueResp { value: 1 });
+
+    let resp = contract.query_donor(&app, &sender).unwrap();
+    assert_eq!(resp.total, coins(15, format!("cw20:{token}")));
+    assert_eq!(resp.donations, 1);
+
+    assert_eq!(cw20_balance(&app, &token, contract.addr()), 15);
+    assert_eq!(cw20_balance(&app, &token, &sender), 5);
+}
+
+#[test]
+fn donate_unsupported_cw20() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::default();
+
+    let token = instantiate_cw20(&mut app, &sender, 20);
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    let err = contract
+        .donate_cw20(&mut app, &sender, &token, 10)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::UnsupportedToken {
+            token: token.to_string()
+        }
+    );
+    assert_eq!(cw20_balance(&app, &token, &sender), 20);
+}
+
+#[test]
+fn withdraw_cw20() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+    let receiver = Addr::unchecked("receiver");
+
+    let mut app = App::default();
+
+    let token = instantiate_cw20(&mut app, &sender, 30);
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    contract
+        .add_cw20_token(&mut app, &owner, &token, 10)
+        .unwrap();
+    contract.donate_cw20(&mut app, &sender, &token, 20).unwrap();
+
+    contract
+        .withdraw_to(
+            &mut app,
+            &owner,
+            &receiver,
+            None,
+            vec![Cw20Coin {
+                address: token.to_string(),
+                amount: Uint128::new(5),
+            }],
+        )
+        .unwrap();
+
+    assert_eq!(cw20_balance(&app, &token, &receiver), 5);
+    assert_eq!(cw20_balance(&app, &token, contract.addr()), 15);
+
+    // Without any listed funds the accepted tokens are drained as well
+    let event = contract
+        .withdraw_to(&mut app, &owner, &receiver, None, None)
+        .unwrap();
+    assert_eq!(event.amount, coins(15, format!("cw20:{token}")));
+
+    assert_eq!(cw20_balance(&app, &token, &receiver), 20);
+    assert_eq!(cw20_balance(&app, &token, contract.addr()), 0);
+
+    contract.donate_cw20(&mut app, &sender, &token, 10).unwrap();
+    contract.withdraw(&mut app, &owner).unwrap();
+
+    assert_eq!(cw20_balance(&app, &token, &owner), 10);
+    assert_eq!(cw20_balance(&app, &token, contract.addr()), 0);
+}
+
+#[test]
+fn manage_cw20_tokens() {
+    let owner = Addr::unchecked("owner");
+    let token = Addr::unchecked("token");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    contract
+        .add_cw20_token(&mut app, &owner, &token, 10)
+        .unwrap();
+
+    let resp = contract.query_cw20_tokens(&app).unwrap();
+    assert_eq!(
+        resp,
+        Cw20TokensResp {
+            tokens: vec![Cw20CoinVerified {
+                address: token.clone(),
+                amount: Uint128::new(10),
+            }]
+        }
+    );
+
+    contract
+        .remove_cw20_token(&mut app, &owner, &token)
+        .unwrap();
+
+    let resp = contract.query_cw20_tokens(&app).unwrap();
+    assert_eq!(resp, Cw20TokensResp { tokens: vec![] });
+}
+
+#[test]
+fn query_config() {
+    let owner = Addr::unchecked("owner");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    let resp = contract.query_config(&app).unwrap();
+    assert_eq!(
+        resp,
+        ConfigResp {
+            owner: Some(owner),
+            minimal_donation: coins(10, ATOM),
+            cw20_tokens: vec![],
+            strict: false,
+            refund: RefundPolicy::default(),
+            reward_collection: None,
+            milestones: Milestones::default(),
+            governance: None,
+            approval_policy: None,
+            access_mode: AccessMode::Open,
+            rate_limit: None,
+            max_memo_length: DEFAULT_MAX_MEMO_LENGTH,
+        }
+    );
+}
+
+#[test]
+fn update_config() {
+    let owner = Addr::unchecked("owner");
+    let token = Addr::unchecked("token");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    let resp = contract
+        .update_config(
+            &mut app,
+            &owner,
+            ConfigUpdate {
+                accepted_denoms: Some(vec![coin(20, ATOM), coin(5, OSMO)]),
+                cw20_tokens: Some(vec![Cw20Coin {
+                    address: token.to_string(),
+                    amount: Uint128::new(100),
+                }]),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        resp,
+        ConfigUpdateEvent {
+            sender: owner.clone(),
+            changes: vec![
+                ConfigChange {
+                    field: "accepted_denoms".to_owned(),
+                    old: "10atom".to_owned(),
+                    new: "20atom,5osmo".to_owned(),
+                },
+                ConfigChange {
+                    field: "cw20_tokens".to_owned(),
+                    old: "none".to_owned(),
+                    new: "100cw20:token".to_owned(),
+                },
+            ],
+        }
+    );
+
+    let resp = contract.query_config(&app).unwrap();
+    assert_eq!(
+        resp,
+        ConfigResp {
+            owner: Some(owner.clone()),
+            minimal_donation: vec![coin(20, ATOM), coin(5, OSMO)],
+            cw20_tokens: vec![Cw20CoinVerified {
+                address: token,
+                amount: Uint128::new(100),
+            }],
+            strict: false,
+            refund: RefundPolicy::default(),
+            reward_collection: None,
+            milestones: Milestones::default(),
+            governance: None,
+            approval_policy: None,
+            access_mode: AccessMode::Open,
+            rate_limit: None,
+            max_memo_length: DEFAULT_MAX_MEMO_LENGTH,
+        }
+    );
+
+    let resp = contract
+        .update_config(&mut app, &owner, ConfigUpdate::default())
+        .unwrap();
+    assert_eq!(resp.changes, vec![]);
+}
+
+#[test]
+fn unauthorized_update_config() {
+    let owner = Addr::unchecked("owner");
+    let member = Addr::unchecked("member");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    let err = contract
+        .update_config(
+            &mut app,
+            &member,
+            ConfigUpdate {
+                accepted_denoms: Some(vec![]),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::MissingRole { role: Role::Admin });
+}
+
+#[test]
+fn strict_donation() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, vec![coin(20, ATOM), coin(10, OSMO)])
+            .unwrap();
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            strict: true,
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+
+    let err = contract.donate(&mut app, &sender, &[]).unwrap_err();
+    assert_eq!(err, ContractError::NoFunds {});
+
+    let err = contract
+        .donate(&mut app, &sender, &[coin(5, ATOM), coin(10, OSMO)])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::UnsupportedDenom {
+            denom: OSMO.to_owned()
+        }
+    );
+
+    let err = contract
+        .donate(&mut app, &sender, &coins(5, ATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientDonation {
+            required: coin(10, ATOM),
+            sent: coin(5, ATOM),
+        }
+    );
+
+    assert_eq!(
+        app.wrap().query_all_balances(&sender).unwrap(),
+        vec![coin(20, ATOM), coin(10, OSMO)]
+    );
+
+    contract
+        .donate(&mut app, &sender, &coins(10, ATOM))
+        .unwrap();
+    let resp = contract.query_value(&app).unwrap();
+    assert_eq!(resp, ValueResp { value: 1 });
+}
+
+#[test]
+fn strict_cw20_donation() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::default();
+
+    let token = instantiate_cw20(&mut app, &sender, 20);
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            strict: true,
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+
+    contract
+        .add_cw20_token(&mut app, &owner, &token, 10)
+        .unwrap();
+
+    let err = contract
+        .donate_cw20(&mut app, &sender, &token, 5)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientDonation {
+            required: coin(10, format!("cw20:{token}")),
+            sent: coin(5, format!("cw20:{token}")),
+        }
+    );
+    assert_eq!(cw20_balance(&app, &token, &sender), 20);
+}
+
+#[test]
+fn switch_strict_mode() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(10, ATOM))
+            .unwrap();
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    contract.donate(&mut app, &sender, &coins(5, ATOM)).unwrap();
+
+    let resp = contract
+        .update_config(
+            &mut app,
+            &owner,
+            ConfigUpdate {
+                strict: Some(true),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        resp.changes,
+        vec![ConfigChange {
+            field: "strict".to_owned(),
+            old: "false".to_owned(),
+            new: "true".to_owned(),
+        }]
+    );
+
+    let err = contract
+        .donate(&mut app, &sender, &coins(5, ATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientDonation {
+            required: coin(10, ATOM),
+            sent: coin(5, ATOM),
+        }
+    );
+}
+
+#[test]
+fn refund_unsupported_denoms() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, vec![coin(10, ATOM), coin(5, OSMO)])
+            .unwrap();
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            refund: RefundPolicy {
+                unsupported_denoms: true,
+                cap: DonationCap::Uncapped {},
+            },
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+
+    contract
+        .donate(&mut app, &sender, &[coin(10, ATOM), coin(5, OSMO)])
+        .unwrap();
+
+    assert_eq!(
+        app.wrap().query_all_balances(&sender).unwrap(),
+        coins(5, OSMO)
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(contract.addr()).unwrap(),
+        coins(10, ATOM)
+    );
+
+    let resp = contract.query_donor(&app, &sender).unwrap();
+    assert_eq!(resp.total, coins(10, ATOM));
+}
+
+#[test]
+fn refunded_donation_memo() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(5, OSMO))
+            .unwrap();
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            refund: RefundPolicy {
+                unsupported_denoms: true,
+                cap: DonationCap::Uncapped {},
+            },
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+
+    // Nothing is kept, so there is no logged donation to attach the memo to
+    let err = contract
+        .donate_with_memo(&mut app, &sender, &coins(5, OSMO), "Thanks!")
+        .unwrap_err();
+    assert_eq!(err, ContractError::MemoNotLogged {});
+
+    let event = contract.donate(&mut app, &sender, &coins(5, OSMO)).unwrap();
+    assert_eq!(event.refunded, coins(5, OSMO));
+    assert_eq!(
+        app.wrap().query_all_balances(&sender).unwrap(),
+        coins(5, OSMO)
+    );
+
+    let resp = contract.query_donations(&app, None, None).unwrap();
+    assert!(resp.donations.is_empty());
+}
+
+#[test]
+fn refund_over_minimal_donation() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(25, ATOM))
+            .unwrap();
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            refund: RefundPolicy {
+                unsupported_denoms: false,
+                cap: DonationCap::Minimal {},
+            },
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+
+    let event = contract
+        .donate(&mut app, &sender, &coins(25, ATOM))
+        .unwrap();
+    assert_eq!(event.kept, coins(10, ATOM));
+    assert_eq!(event.refunded, coins(15, ATOM));
+
+    assert_eq!(
+        app.wrap().query_all_balances(&sender).unwrap(),
+        coins(15, ATOM)
+    );
+    let resp = contract.query_value(&app).unwrap();
+    assert_eq!(resp, ValueResp { value: 1 });
+}
+
+#[test]
+fn refund_over_maximum_donation() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(25, ATOM))
+            .unwrap();
+    });
+
+    let token = instantiate_cw20(&mut app, &sender, 50);
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            refund: RefundPolicy {
+                unsupported_denoms: false,
+                cap: DonationCap::Maximum {
+                    amounts: vec![coin(20, ATOM), coin(30, format!("cw20:{token}"))],
+                },
+            },
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+
+    contract
+        .add_cw20_token(&mut app, &owner, &token, 10)
+        .unwrap();
+
+    contract
+        .donate(&mut app, &sender, &coins(25, ATOM))
+        .unwrap();
+    contract.donate_cw20(&mut app, &sender, &token, 50).unwrap();
+
+    assert_eq!(
+        app.wrap().query_all_balances(&sender).unwrap(),
+        coins(5, ATOM)
+    );
+    assert_eq!(cw20_balance(&app, &token, &sender), 20);
+    assert_eq!(cw20_balance(&app, &token, contract.addr()), 30);
+
+    let resp = contract.query_donor(&app, &sender).unwrap();
+    assert_eq!(
+        resp.total,
+        vec![coin(20, ATOM), coin(30, format!("cw20:{token}"))]
+    );
+}
+
+fn instantiate_campaign(app: &mut App, owner: &Addr, goal: Vec<Coin>) -> CountingContract {
+    let height = app.block_info().height;
+    let code_id = CountingContract::store_code(app);
+
+    CountingContract::instantiate_with_msg(
+        app,
+        code_id,
+        owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            campaign: Some(Campaign {
+                goal,
+                start: Some(Scheduled::AtHeight(height + 10)),
+                end: Scheduled::AtHeight(height + 100),
+            }),
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap()
+}
+
+#[test]
+fn campaign_donation_window() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(30, ATOM))
+            .unwrap();
+    });
+
+    let contract = instantiate_campaign(&mut app, &owner, coins(100, ATOM));
+
+    let err = contract
+        .donate(&mut app, &sender, &coins(10, ATOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::CampaignNotStarted {});
+
+    app.update_block(|block| block.height += 10);
+    contract
+        .donate(&mut app, &sender, &coins(10, ATOM))
+        .unwrap();
+
+    app.update_block(|block| block.height += 90);
+    let err = contract
+        .donate(&mut app, &sender, &coins(10, ATOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::CampaignEnded {});
+
+    assert_eq!(
+        app.wrap().query_all_balances(&sender).unwrap(),
+        coins(20, ATOM)
+    );
+    let resp = contract.query_value(&app).unwrap();
+    assert_eq!(resp, ValueResp { value: 1 });
+}
+
+#[test]
+fn campaign_already_ended() {
+    let owner = Addr::unchecked("owner");
+
+    let mut app = App::default();
+    let height = app.block_info().height;
+
+    let code_id = CountingContract::store_code(&mut app);
+    let err = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            campaign: Some(Campaign {
+                goal: coins(100, ATOM),
+                start: None,
+                end: Scheduled::AtHeight(height),
+            }),
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap_err();
+
+    assert_eq!(err, ContractError::CampaignEnded {});
+}
+
+#[test]
+fn invalid_campaign() {
+    let owner = Addr::unchecked("owner");
+
+    let mut app = App::default();
+    let height = app.block_info().height;
+    let code_id = CountingContract::store_code(&mut app);
+
+    let mut instantiate = |campaign| {
+        CountingContract::instantiate_with_msg(
+            &mut app,
+            code_id,
+            &owner,
+            "Counting contract",
+            &InstantiateMsg {
+                minimal_donation: coin(10, ATOM),
+                campaign: Some(campaign),
+                ..Default::default()
+            },
+            None,
+        )
+        .unwrap_err()
+    };
+
+    let err = instantiate(Campaign {
+        goal: vec![],
+        start: None,
+        end: Scheduled::AtHeight(height + 100),
+    });
+    assert_eq!(
+        err,
+        ContractError::InvalidCampaign {
+            reason: "goal has to be above zero".to_owned(),
+        }
+    );
+
+    let err = instantiate(Campaign {
+        goal: coins(100, OSMO),
+        start: None,
+        end: Scheduled::AtHeight(height + 100),
+    });
+    assert_eq!(
+        err,
+        ContractError::InvalidCampaign {
+            reason: format!("goal denom {OSMO} is not accepted"),
+        }
+    );
+
+    let err = instantiate(Campaign {
+        goal: coins(100, ATOM),
+        start: Some(Scheduled::AtHeight(height + 100)),
+        end: Scheduled::AtHeight(height + 100),
+    });
+    assert_eq!(
+        err,
+        ContractError::InvalidCampaign {
+            reason: "start has to be before the end".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn campaign_succeeded() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(100, ATOM))
+            .unwrap();
+    });
+
+    let contract = instantiate_campaign(&mut app, &owner, coins(100, ATOM));
+    app.update_block(|block| block.height += 10);
+
+    contract
+        .donate(&mut app, &sender, &coins(100, ATOM))
+        .unwrap();
+
+    let err = contract.withdraw(&mut app, &owner).unwrap_err();
+    assert_eq!(err, ContractError::CampaignNotFinished {});
+
+    app.update_block(|block| block.height += 90);
+
+    let err = contract.claim_refund(&mut app, &sender).unwrap_err();
+    assert_eq!(err, ContractError::CampaignGoalReached {});
+
+    contract.withdraw(&mut app, &owner).unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(100, ATOM)
+    );
+}
+
+#[test]
+fn campaign_failed() {
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender1, coins(30, ATOM))
+            .unwrap();
+    });
+
+    let token = instantiate_cw20(&mut app, &sender1, 50);
+    let contract = instantiate_campaign(
+        &mut app,
+        &owner,
+        vec![coin(100, ATOM), coin(50, format!("cw20:{token}"))],
+    );
+    contract
+        .add_cw20_token(&mut app, &owner, &token, 10)
+        .unwrap();
+    app.update_block(|block| block.height += 10);
+
+    contract
+        .donate(&mut app, &sender1, &coins(20, ATOM))
+        .unwrap();
+    contract
+        .donate(&mut app, &sender1, &coins(10, ATOM))
+        .unwrap();
+    contract
+        .donate_cw20(&mut app, &sender1, &token, 50)
+        .unwrap();
+
+    let err = contract.claim_refund(&mut app, &sender1).unwrap_err();
+    assert_eq!(err, ContractError::CampaignNotFinished {});
+
+    app.update_block(|block| block.height += 90);
+
+    let err = contract.withdraw(&mut app, &owner).unwrap_err();
+    assert_eq!(err, ContractError::CampaignGoalNotReached {});
+
+    let err = contract.claim_refund(&mut app, &sender2).unwrap_err();
+    assert_eq!(err, ContractError::NothingToRefund {});
+
+    contract.claim_refund(&mut app, &sender1).unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&sender1).unwrap(),
+        coins(30, ATOM)
+    );
+    assert_eq!(cw20_balance(&app, &token, &sender1), 50);
+
+    let err = contract.claim_refund(&mut app, &sender1).unwrap_err();
+    assert_eq!(err, ContractError::NothingToRefund {});
+}
+
+#[test]
+fn query_campaign() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(30, ATOM))
+            .unwrap();
+    });
+
+    let height = app.block_info().height;
+    let contract = instantiate_campaign(&mut app, &owner, coins(100, ATOM));
+
+    let resp = contract.query_campaign(&app).unwrap();
+    assert_eq!(
+        resp,
+        CampaignResp {
+            campaign: Some(Campaign {
+                goal: coins(100, ATOM),
+                start: Some(Scheduled::AtHeight(height + 10)),
+                end: Scheduled::AtHeight(height + 100),
+            }),
+            phase: Some(CampaignPhase::NotStarted),
+            raised: vec![],
+        }
+    );
+
+    app.update_block(|block| block.height += 10);
+    contract
+        .donate(&mut app, &sender, &coins(30, ATOM))
+        .unwrap();
+
+    let resp = contract.query_campaign(&app).unwrap();
+    assert_eq!(resp.phase, Some(CampaignPhase::Open));
+    assert_eq!(resp.raised, coins(30, ATOM));
+
+    app.update_block(|block| block.height += 90);
+    let resp = contract.query_campaign(&app).unwrap();
+    assert_eq!(resp.phase, Some(CampaignPhase::Failed));
+}
+
+#[test]
+fn named_counters() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, vec![coin(30, ATOM), coin(20, OSMO)])
+            .unwrap();
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    contract
+        .create_counter(&mut app, &owner, "trees", coin(10, OSMO), None)
+        .unwrap();
+
+    contract
+        .donate_to(&mut app, &sender, "trees", &coins(10, OSMO))
+        .unwrap();
+    contract
+        .donate_to(&mut app, &sender, "trees", &coins(10, ATOM))
+        .unwrap();
+    contract
+        .donate(&mut app, &sender, &coins(10, OSMO))
+        .unwrap();
+    contract
+        .donate(&mut app, &sender, &coins(10, ATOM))
+        .unwrap();
+
+    let resp = contract.query_counter_value(&app, "trees").unwrap();
+    assert_eq!(resp, ValueResp { value: 1 });
+    let resp = contract.query_value(&app).unwrap();
+    assert_eq!(resp, ValueResp { value: 1 });
+
+    let err = contract
+        .donate_to(&mut app, &sender, "whales", &coins(10, ATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::CounterNotFound {
+            counter_id: "whales".to_owned()
+        }
+    );
+}
+
+#[test]
+fn create_counter() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    let err = contract
+        .create_counter(&mut app, &sender, "trees", coin(10, OSMO), None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::MissingRole { role: Role::Admin });
+
+    let err = contract
+        .create_counter(&mut app, &owner, "", coin(10, OSMO), None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::EmptyCounterId {});
+
+    let err = contract
+        .create_counter(&mut app, &owner, "default", coin(10, OSMO), None)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::CounterExists {
+            counter_id: "default".to_owned()
+        }
+    );
+
+    contract
+        .create_counter(&mut app, &owner, "trees", coin(10, OSMO), &sender)
+        .unwrap();
+
+    let resp = contract.query_counters(&app, None, None).unwrap();
+    assert_eq!(
+        resp.counters,
+        vec![
+            CounterResp {
+                counter_id: "default".to_owned(),
+                value: 0,
+                minimal_donation: None,
+                owner: None,
+            },
+            CounterResp {
+                counter_id: "trees".to_owned(),
+                value: 0,
+                minimal_donation: Some(coin(10, OSMO)),
+                owner: Some(sender),
+            },
+        ]
+    );
+}
+
+#[test]
+fn reset_named_counter() {
+    let owner = Addr::unchecked("owner");
+    let counter_owner = Addr::unchecked("counter_owner");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    contract
+        .create_counter(&mut app, &owner, "trees", coin(10, OSMO), &counter_owner)
+        .unwrap();
+
+    contract
+        .reset_counter(&mut app, &counter_owner, "trees", 5)
+        .unwrap();
+    let err = contract.reset(&mut app, &counter_owner, 5).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MissingRole {
+            role: Role::Resetter
+        }
+    );
+
+    contract
+        .reset_counter(&mut app, &owner, "trees", 7)
+        .unwrap();
+
+    let resp = contract.query_counter_value(&app, "trees").unwrap();
+    assert_eq!(resp, ValueResp { value: 7 });
+    let resp = contract.query_value(&app).unwrap();
+    assert_eq!(resp, ValueResp { value: 0 });
+}
+
+#[test]
+fn query_counters_paginated() {
+    let owner = Addr::unchecked("owner");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    for counter_id in ["birds", "trees", "whales"] {
+        contract
+            .create_counter(&mut app, &owner, counter_id, coin(10, OSMO), None)
+            .unwrap();
+    }
+
+    let resp = contract.query_counters(&app, None, 2).unwrap();
+    let ids: Vec<_> = resp.counters.into_iter().map(|c| c.counter_id).collect();
+    assert_eq!(ids, ["birds", "default"]);
+
+    let resp = contract.query_counters(&app, "default", 2).unwrap();
+    let ids: Vec<_> = resp.counters.into_iter().map(|c| c.counter_id).collect();
+    assert_eq!(ids, ["trees", "whales"]);
+}
+
+#[test]
+fn counter_history() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(20, ATOM))
+            .unwrap();
+    });
+
+    let height = app.block_info().height;
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    contract
+        .donate(&mut app, &sender, &coins(10, ATOM))
+        .unwrap();
+    app.update_block(|block| block.height += 1);
+    contract
+        .donate(&mut app, &sender, &coins(10, ATOM))
+        .unwrap();
+    app.update_block(|block| block.height += 1);
+    contract.reset(&mut app, &owner, 10).unwrap();
+
+    assert_eq!(
+        contract.query_value_at(&app, height).unwrap(),
+        ValueResp { value: 1 }
+    );
+    assert_eq!(
+        contract.query_value_at(&app, height + 1).unwrap(),
+        ValueResp { value: 2 }
+    );
+    assert_eq!(
+        contract.query_value_at(&app, height + 2).unwrap(),
+        ValueResp { value: 10 }
+    );
+    contract.query_value_at(&app, height - 1).unwrap_err();
+    contract.query_value_at(&app, u64::MAX).unwrap_err();
+
+    let resp = contract.query_history(&app, None, None, None).unwrap();
+    assert_eq!(
+        resp.entries,
+        vec![
+            HistoryEntry {
+                id: 0,
+                height,
+                value: 0,
+                cause: ChangeCause::Create,
+                sender: Some(owner.clone()),
+            },
+            HistoryEntry {
+                id: 1,
+                height,
+                value: 1,
+                cause: ChangeCause::Donate,
+                sender: Some(sender.clone()),
+            },
+            HistoryEntry {
+                id: 2,
+                height: height + 1,
+                value: 2,
+                cause: ChangeCause::Donate,
+                sender: Some(sender),
+            },
+            HistoryEntry {
+                id: 3,
+                height: height + 2,
+                value: 10,
+                cause: ChangeCause::Reset,
+                sender: Some(owner),
+            },
+        ]
+    );
+
+    let resp = contract.query_history(&app, None, 1, 2).unwrap();
+    let ids: Vec<_> = resp.entries.into_iter().map(|e| e.id).collect();
+    assert_eq!(ids, [2, 3]);
+}
+
+/// Token which reports a balance but rejects every transfer
+fn instantiate_broken_cw20(app: &mut App, owner: &Addr) -> Addr {
+    let code_id = app.store_code(Box::new(ContractWrapper::new(
+        |_: DepsMut, _: Env, _: MessageInfo, _: Cw20ExecuteMsg| -> StdResult<Response> {
+            Err(StdError::generic_err("Transfers are disabled"))
+        },
+        |_: DepsMut, _: Env, _: MessageInfo, _: Empty| -> StdResult<Response> {
+            Ok(Response::new())
+        },
+        |_: Deps, _: Env, _: Cw20QueryMsg| -> StdResult<Binary> {
+            to_json_binary(&BalanceResponse {
+                balance: Uint128::new(10),
+            })
+        },
+    )));
+
+    app.instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "Broken token", None)
+        .unwrap()
+}
+
+/// Token which fails transfers until `height`, always reporting a balance of 10
+fn instantiate_paused_cw20(app: &mut App, owner: &Addr, height: u64) -> Addr {
+    let code_id = app.store_code(Box::new(ContractWrapper::new(
+        |deps: DepsMut, env: Env, _: MessageInfo, _: Cw20ExecuteMsg| -> StdResult<Response> {
+            let height: u64 = from_json(deps.storage.get(b"height").unwrap())?;
+            if env.block.height < height {
+                return Err(StdError::generic_err("Transfers are paused"));
+            }
+            Ok(Response::new())
+        },
+        |deps: DepsMut, _: Env, _: MessageInfo, height: u64| -> StdResult<Response> {
+            deps.storage.set(b"height", &to_json_vec(&height)?);
+            Ok(Response::new())
+        },
+        |_: Deps, _: Env, _: Cw20QueryMsg| -> StdResult<Binary> {
+            to_json_binary(&BalanceResponse {
+                balance: Uint128::new(10),
+            })
+        },
+    )));
+
+    app.instantiate_contract(code_id, owner.clone(), &height, &[], "Paused token", None)
+        .unwrap()
+}
+
+#[test]
+fn withdrawal_log() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+    let receiver = Addr::unchecked("receiver");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(20, ATOM))
+            .unwrap();
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    contract
+        .donate(&mut app, &sender, &coins(20, ATOM))
+        .unwrap();
+
+    contract
+        .withdraw_to(&mut app, &owner, &receiver, coins(5, ATOM), None)
+        .unwrap();
+    app.update_block(|block| block.height += 1);
+    contract.withdraw(&mut app, &owner).unwrap();
+
+    let height = app.block_info().height;
+    let resp = contract.query_withdrawals(&app, None, None).unwrap();
+    assert_eq!(
+        resp.withdrawals,
+        vec![
+            WithdrawalResp {
+                id: 0,
+                receiver,
+                amount: coins(5, ATOM),
+                height: height - 1,
+                outcome: WithdrawalOutcome::Succeeded {},
+            },
+            WithdrawalResp {
+                id: 1,
+                receiver: owner,
+                amount: coins(15, ATOM),
+                height,
+                outcome: WithdrawalOutcome::Succeeded {},
+            },
+        ]
+    );
+
+    let resp = contract.query_withdrawals(&app, 0, None).unwrap();
+    assert_eq!(resp.withdrawals.len(), 1);
+    assert_eq!(resp.withdrawals[0].id, 1);
+}
+
+#[test]
+fn failed_withdrawal() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(10, ATOM))
+            .unwrap();
+    });
+
+    let token = instantiate_broken_cw20(&mut app, &owner);
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    contract
+        .add_cw20_token(&mut app, &owner, &token, 10)
+        .unwrap();
+    contract
+        .donate(&mut app, &sender, &coins(10, ATOM))
+        .unwrap();
+
+    let resp = app
+        .execute_contract(
+            owner.clone(),
+            contract.addr().clone(),
+            &ExecMsg::Withdraw {},
+            &[],
+        )
+        .unwrap();
+    resp.assert_event(
+        &Event::new("wasm-withdraw-result")
+            .add_attribute("id", "0")
+            .add_attribute("amount", "10atom")
+            .add_attribute("success", "true"),
+    );
+    resp.assert_event(
+        &Event::new("wasm-withdraw-result")
+            .add_attribute("id", "1")
+            .add_attribute("amount", format!("10cw20:{token}"))
+            .add_attribute("success", "false"),
+    );
+
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(10, ATOM)
+    );
+
+    let resp = contract.query_withdrawals(&app, None, None).unwrap();
+    assert_eq!(resp.withdrawals[0].outcome, WithdrawalOutcome::Succeeded {});
+    assert!(matches!(
+        resp.withdrawals[1].outcome,
+        WithdrawalOutcome::Failed { .. }
+    ));
+}
+
+#[test]
+fn donate_event() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, vec![coin(20, ATOM), coin(5, OSMO)])
+            .unwrap();
+    });
+
+    let token = instantiate_cw20(&mut app, &sender, 10);
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            counter: 3,
+            minimal_donation: coin(10, ATOM),
+            refund: RefundPolicy {
+                unsupported_denoms: true,
+                cap: DonationCap::Uncapped {},
+            },
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+    contract
+        .add_cw20_token(&mut app, &owner, &token, 20)
+        .unwrap();
+
+    let event = contract
+        .donate(&mut app, &sender, &[coin(20, ATOM), coin(5, OSMO)])
+        .unwrap();
+    assert_eq!(
+        event,
+        DonateEvent {
+            sender: sender.clone(),
+            counter_id: "default".to_owned(),
+            kept: coins(20, ATOM),
+            refunded: coins(5, OSMO),
+            fee: vec![],
+            counter_before: 3,
+            counter_after: 4,
+        }
+    );
+
+    let event = contract.donate_cw20(&mut app, &sender, &token, 10).unwrap();
+    assert_eq!(
+        event,
+        DonateEvent {
+            sender,
+            counter_id: "default".to_owned(),
+            kept: coins(10, format!("cw20:{token}")),
+            refunded: vec![],
+            fee: vec![],
+            counter_before: 4,
+            counter_after: 4,
+        }
+    );
+}
+
+#[test]
+fn reset_and_withdraw_events() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+    let receiver = Addr::unchecked("receiver");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(10, ATOM))
+            .unwrap();
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    contract
+        .donate(&mut app, &sender, &coins(10, ATOM))
+        .unwrap();
+
+    let event = contract.reset(&mut app, &owner, 7).unwrap();
+    assert_eq!(
+        event,
+        ResetEvent {
+            sender: owner.clone(),
+            counter_id: "default".to_owned(),
+            counter_before: 1,
+            counter_after: 7,
+        }
+    );
+
+    let event = contract
+        .withdraw_to(&mut app, &owner, &receiver, coins(4, ATOM), None)
+        .unwrap();
+    assert_eq!(
+        event,
+        WithdrawToEvent {
+            sender: owner.clone(),
+            receiver,
+            amount: coins(4, ATOM),
+        }
+    );
+
+    let event = contract.withdraw(&mut app, &owner).unwrap();
+    assert_eq!(
+        event,
+        WithdrawEvent {
+            sender: owner.clone(),
+            receiver: owner,
+            amount: coins(6, ATOM),
+        }
+    );
+}
+
+fn instantiate_reward_collection(app: &mut App, owner: &Addr, minter: &Addr) -> Addr {
+    type Collection<'a> = cw721_base::Cw721Contract<'a, RewardMetadata, Empty, Empty, Empty>;
+
+    let code_id = app.store_code(Box::new(ContractWrapper::new(
+        |deps, env, info, msg| Collection::default().execute(deps, env, info, msg),
+        |deps, env, info, msg| Collection::default().instantiate(deps, env, info, msg),
+        |deps, env, msg| Collection::default().query(deps, env, msg),
+    )));
+
+    app.instantiate_contract(
+        code_id,
+        owner.clone(),
+        &cw721_base::InstantiateMsg {
+            name: "Donor rewards".to_owned(),
+            symbol: "DONOR".to_owned(),
+            minter: minter.to_string(),
+        },
+        &[],
+        "Donor rewards",
+        None,
+    )
+    .unwrap()
+}
+
+#[track_caller]
+fn reward(app: &App, collection: &Addr, token_id: &str) -> AllNftInfoResponse<RewardMetadata> {
+    app.wrap()
+        .query_wasm_smart(
+            collection,
+            &cw721_base::QueryMsg::<Empty>::AllNftInfo {
+                token_id: token_id.to_owned(),
+                include_expired: None,
+            },
+        )
+        .unwrap()
+}
+
+#[test]
+fn reward_milestones() {
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender1, coins(25, ATOM))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &sender2, coins(20, ATOM))
+            .unwrap();
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            milestones: Milestones {
+                every: Some(2),
+                values: vec![3],
+            },
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+
+    let collection = instantiate_reward_collection(&mut app, &owner, contract.addr());
+    contract
+        .update_config(
+            &mut app,
+            &owner,
+            ConfigUpdate {
+                reward_collection: Some(Some(collection.to_string())),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    let height = app.block_info().height;
+    contract
+        .donate(&mut app, &sender1, &coins(10, ATOM))
+        .unwrap();
+    contract
+        .donate(&mut app, &sender2, &coins(10, ATOM))
+        .unwrap();
+    contract
+        .donate(&mut app, &sender1, &coins(5, ATOM))
+        .unwrap();
+    contract
+        .donate(&mut app, &sender1, &coins(10, ATOM))
+        .unwrap();
+    contract
+        .donate(&mut app, &sender2, &coins(10, ATOM))
+        .unwrap();
+
+    let resp = reward(&app, &collection, "0");
+    assert_eq!(resp.access.owner, sender2.as_str());
+    assert_eq!(
+        resp.info.extension,
+        RewardMetadata {
+            counter_id: "default".to_owned(),
+            counter: 2,
+            height,
+        }
+    );
+
+    let resp = reward(&app, &collection, "1");
+    assert_eq!(resp.access.owner, sender1.as_str());
+    assert_eq!(resp.info.extension.counter, 3);
+
+    let resp = reward(&app, &collection, "2");
+    assert_eq!(resp.access.owner, sender2.as_str());
+    assert_eq!(resp.info.extension.counter, 4);
+
+    let resp: NumTokensResponse = app
+        .wrap()
+        .query_wasm_smart(&collection, &cw721_base::QueryMsg::<Empty>::NumTokens {})
+        .unwrap();
+    assert_eq!(resp.count, 3);
+
+    contract
+        .update_config(
+            &mut app,
+            &owner,
+            ConfigUpdate {
+                reward_collection: Some(None),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let resp = contract.query_config(&app).unwrap();
+    assert_eq!(resp.reward_collection, None);
+}
+
+#[test]
+fn rejected_reward_mint() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(10, ATOM))
+            .unwrap();
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            milestones: Milestones {
+                every: Some(1),
+                values: vec![],
+            },
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+
+    // The contract is not the minter of the collection
+    let collection = instantiate_reward_collection(&mut app, &owner, &owner);
+    contract
+        .update_config(
+            &mut app,
+            &owner,
+            ConfigUpdate {
+                reward_collection: Some(Some(collection.to_string())),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    let resp = app
+        .execute_contract(
+            sender.clone(),
+            contract.addr().clone(),
+            &ExecMsg::Donate {
+                counter_id: None,
+                memo: None,
+            },
+            &coins(10, ATOM),
+        )
+        .unwrap();
+    let event = resp
+        .events
+        .iter()
+        .find(|event| event.ty == "wasm-reward-result")
+        .unwrap();
+    assert!(event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "success" && attr.value == "false"));
+
+    let resp = contract.query_value(&app).unwrap();
+    assert_eq!(resp, ValueResp { value: 1 });
+    assert_eq!(
+        app.wrap().query_all_balances(contract.addr()).unwrap(),
+        coins(10, ATOM)
+    );
+
+    let resp: NumTokensResponse = app
+        .wrap()
+        .query_wasm_smart(&collection, &cw721_base::QueryMsg::<Empty>::NumTokens {})
+        .unwrap();
+    assert_eq!(resp.count, 0);
+}
+
+#[test]
+fn invalid_milestones() {
+    let owner = Addr::unchecked("owner");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    let err = contract
+        .update_config(
+            &mut app,
+            &owner,
+            ConfigUpdate {
+                milestones: Some(Milestones {
+                    every: Some(0),
+                    values: vec![],
+                }),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidMilestones {});
+}
+
+#[test]
+fn distribute() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+    let carol = Addr::unchecked("carol");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(202, ATOM))
+            .unwrap();
+    });
+
+    let token = instantiate_cw20(&mut app, &sender, 20);
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    contract
+        .add_cw20_token(&mut app, &owner, &token, 10)
+        .unwrap();
+    contract
+        .donate(&mut app, &sender, &coins(101, ATOM))
+        .unwrap();
+    contract.donate_cw20(&mut app, &sender, &token, 20).unwrap();
+
+    let event = contract
+        .set_beneficiaries(
+            &mut app,
+            &owner,
+            &[(&alice, 5000), (&bob, 3333), (&carol, 1667)],
+            &owner,
+        )
+        .unwrap();
+    assert_eq!(
+        event,
+        SetBeneficiariesEvent {
+            sender: owner.clone(),
+            beneficiaries: vec![
+                (alice.clone(), 5000),
+                (bob.clone(), 3333),
+                (carol.clone(), 1667)
+            ],
+            remainder: owner.clone(),
+        }
+    );
+
+    let resp = contract.query_beneficiaries(&app).unwrap();
+    assert_eq!(
+        resp,
+        BeneficiariesResp {
+            beneficiaries: vec![
+                BeneficiaryResp {
+                    address: alice.clone(),
+                    weight_bps: 5000,
+                },
+                BeneficiaryResp {
+                    address: bob.clone(),
+                    weight_bps: 3333,
+                },
+                BeneficiaryResp {
+                    address: carol.clone(),
+                    weight_bps: 1667,
+                },
+            ],
+            remainder: Some(owner.clone()),
+        }
+    );
+
+    let event = contract.distribute(&mut app, &owner).unwrap();
+    assert_eq!(
+        event,
+        DistributeEvent {
+            sender: owner.clone(),
+            amount: vec![coin(101, ATOM), coin(20, format!("cw20:{token}")),],
+        }
+    );
+
+    assert_eq!(
+        app.wrap().query_all_balances(&alice).unwrap(),
+        coins(50, ATOM)
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&bob).unwrap(),
+        coins(33, ATOM)
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&carol).unwrap(),
+        coins(16, ATOM)
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(2, ATOM)
+    );
+    assert_eq!(cw20_balance(&app, &token, &alice), 10);
+    assert_eq!(cw20_balance(&app, &token, &bob), 6);
+    assert_eq!(cw20_balance(&app, &token, &carol), 3);
+    assert_eq!(cw20_balance(&app, &token, &owner), 1);
+    assert_eq!(
+        app.wrap().query_all_balances(contract.addr()).unwrap(),
+        vec![]
+    );
+
+    let resp = contract.query_withdrawals(&app, None, None).unwrap();
+    assert_eq!(resp.withdrawals.len(), 8);
+    assert!(resp
+        .withdrawals
+        .iter()
+        .all(|withdrawal| withdrawal.outcome == WithdrawalOutcome::Succeeded {}));
+
+    // Dust of a remainder which is a beneficiary too is sent with its share
+    contract
+        .set_beneficiaries(&mut app, &owner, &[(&alice, 5000), (&bob, 5000)], &alice)
+        .unwrap();
+    contract
+        .donate(&mut app, &sender, &coins(101, ATOM))
+        .unwrap();
+    contract.distribute(&mut app, &owner).unwrap();
+
+    assert_eq!(
+        app.wrap().query_all_balances(&alice).unwrap(),
+        coins(101, ATOM)
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&bob).unwrap(),
+        coins(83, ATOM)
+    );
+
+    let resp = contract.query_withdrawals(&app, 7, None).unwrap();
+    assert_eq!(resp.withdrawals.len(), 2);
+}
+
+#[test]
+fn invalid_beneficiaries() {
+    let owner = Addr::unchecked("owner");
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    let err = contract.distribute(&mut app, &owner).unwrap_err();
+    assert_eq!(err, ContractError::NoBeneficiaries {});
+
+    let err = contract
+        .set_beneficiaries(&mut app, &owner, &[(&alice, 5000), (&bob, 4999)], &owner)
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidWeights { total: 9999 });
+
+    let err = contract
+        .set_beneficiaries(&mut app, &owner, &[], &owner)
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidWeights { total: 0 });
+
+    let err = contract
+        .set_beneficiaries(&mut app, &owner, &[(&alice, 5000), (&alice, 5000)], &owner)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DuplicateBeneficiary {
+            address: alice.to_string(),
+        }
+    );
+
+    let err = contract
+        .set_beneficiaries(&mut app, &alice, &[(&alice, 10000)], &alice)
+        .unwrap_err();
+    assert_eq!(err, ContractError::MissingRole { role: Role::Admin });
+
+    let resp = contract.query_beneficiaries(&app).unwrap();
+    assert_eq!(
+        resp,
+        BeneficiariesResp {
+            beneficiaries: vec![],
+            remainder: None,
+        }
+    );
+
+    contract
+        .set_beneficiaries(&mut app, &owner, &[(&alice, 10000)], &owner)
+        .unwrap();
+
+    let err = contract.distribute(&mut app, &alice).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MissingRole {
+            role: Role::Treasurer,
+        }
+    );
+}
+
+#[test]
+fn donation_fee() {
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+    let collector = Addr::unchecked("collector");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, vec![coin(105, ATOM), coin(100, OSMO)])
+            .unwrap();
+    });
+
+    let token = instantiate_cw20(&mut app, &sender, 40);
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            fee_bps: 250,
+            fee_collector: Some(collector.to_string()),
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+    contract
+        .add_cw20_token(&mut app, &owner, &token, 10)
+        .unwrap();
+
+    // Kept coins of denoms which are not accepted are not charged
+    let event = contract
+        .donate(&mut app, &sender, &[coin(100, ATOM), coin(100, OSMO)])
+        .unwrap();
+    assert_eq!(event.kept, [coin(100, ATOM), coin(100, OSMO)]);
+    assert_eq!(event.fee, coins(2, ATOM));
+
+    // Donations which are not counted are kept whole
+    let event = contract.donate(&mut app, &sender, &coins(5, ATOM)).unwrap();
+    assert_eq!(event.fee, vec![]);
+
+    let event = contract.donate_cw20(&mut app, &sender, &token, 40).unwrap();
+    assert_eq!(event.fee, coins(1, format!("cw20:{token}")));
+
+    assert_eq!(
+        app.wrap().query_all_balances(&collector).unwrap(),
+        coins(2, ATOM)
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(contract.addr()).unwrap(),
+        [coin(103, ATOM), coin(100, OSMO)]
+    );
+    assert_eq!(cw20_balance(&app, &token, &collector), 1);
+    assert_eq!(cw20_balance(&app, &token, contract.addr()), 39);
+
+    let resp = contract.query_fee(&app).unwrap();
+    assert_eq!(
+        resp,
+        FeeResp {
+            fee_bps: 250,
+            max_fee_bps: 250,
+            fee_collector: Some(collector),
+            fee_admin: None,
+            collected: vec![coin(2, ATOM), coin(1, format!("cw20:{token}"))],
+        }
+    );
+}
+
+#[test]
+fn update_fee() {
+    let owner = Addr::unchecked("owner");
+    let platform = Addr::unchecked("platform");
+    let collector = Addr::unchecked("collector");
+    let treasury = Addr::unchecked("treasury");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let instantiate = |app: &mut App, fee_bps, max_fee_bps, fee_collector: Option<&Addr>| {
+        CountingContract::instantiate_with_msg(
+            app,
+            code_id,
+            &owner,
+            "Counting contract",
+            &InstantiateMsg {
+                minimal_donation: coin(10, ATOM),
+                fee_bps,
+                fee_collector: fee_collector.map(Addr::to_string),
+                max_fee_bps,
+                fee_admin: Some(platform.to_string()),
+                ..Default::default()
+            },
+            None,
+        )
+    };
+
+    let err = instantiate(&mut app, 100, None, None).unwrap_err();
+    assert_eq!(err, ContractError::MissingFeeCollector {});
+
+    let err = instantiate(&mut app, 300, Some(200), Some(&collector)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::FeeTooHigh {
+            fee_bps: 300,
+            max_fee_bps: 200,
+        }
+    );
+
+    let err = instantiate(&mut app, 300, Some(20000), Some(&collector)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::FeeTooHigh {
+            fee_bps: 20000,
+            max_fee_bps: 10000,
+        }
+    );
+
+    let contract = instantiate(&mut app, 100, Some(500), Some(&collector)).unwrap();
+
+    let err = contract
+        .update_fee(&mut app, &owner, 0, None, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotFeeAdmin {});
+
+    let err = contract
+        .update_fee(&mut app, &platform, 600, None, None)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::FeeTooHigh {
+            fee_bps: 600,
+            max_fee_bps: 500,
+        }
+    );
+
+    let event = contract
+        .update_fee(&mut app, &platform, 500, &treasury, &owner)
+        .unwrap();
+    assert_eq!(
+        event,
+        FeeUpdateEvent {
+            sender: platform.clone(),
+            changes: vec![
+                ConfigChange {
+                    field: "fee_bps".to_owned(),
+                    old: "100".to_owned(),
+                    new: "500".to_owned(),
+                },
+                ConfigChange {
+                    field: "fee_collector".to_owned(),
+                    old: collector.to_string(),
+                    new: treasury.to_string(),
+                },
+                ConfigChange {
+                    field: "fee_admin".to_owned(),
+                    old: platform.to_string(),
+                    new: owner.to_string(),
+                },
+            ],
+        }
+    );
+
+    let err = contract
+        .update_fee(&mut app, &platform, 0, None, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotFeeAdmin {});
+
+    let resp = contract.query_fee(&app).unwrap();
+    assert_eq!(
+        resp,
+        FeeResp {
+            fee_bps: 500,
+            max_fee_bps: 500,
+            fee_collector: Some(treasury),
+            fee_admin: Some(owner),
+            collected: vec![],
+        }
+    );
+}
+
+#[test]
+fn ibc_channels() {
+    let owner = Addr::unchecked("owner");
+    let treasurer = Addr::unchecked("treasurer");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    contract
+        .grant_role(&mut app, &owner, Role::Treasurer, &treasurer)
+        .unwrap();
+
+    let err = contract
+        .add_ibc_channel(&mut app, &treasurer, "channel-0")
+        .unwrap_err();
+    assert_eq!(err, ContractError::MissingRole { role: Role::Admin });
+
+    contract
+        .add_ibc_channel(&mut app, &owner, "channel-0")
+        .unwrap();
+    contract
+        .add_ibc_channel(&mut app, &owner, "channel-7")
+        .unwrap();
+
+    let resp = contract.query_ibc_channels(&app).unwrap();
+    assert_eq!(
+        resp,
+        IbcChannelsResp {
+            channels: vec!["channel-0".to_owned(), "channel-7".to_owned()],
+        }
+    );
+
+    let err = contract
+        .withdraw_ibc(
+            &mut app,
+            &treasurer,
+            "channel-1",
+            "osmo1treasury",
+            &coins(10, ATOM),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ChannelNotAllowed {
+            channel_id: "channel-1".to_owned(),
+        }
+    );
+
+    contract
+        .remove_ibc_channel(&mut app, &owner, "channel-7")
+        .unwrap();
+
+    let err = contract
+        .withdraw_ibc(
+            &mut app,
+            &treasurer,
+            "channel-7",
+            "osmo1treasury",
+            &coins(10, ATOM),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ChannelNotAllowed {
+            channel_id: "channel-7".to_owned(),
+        }
+    );
+
+    // Multitest has no IBC, so only the checks before sending can be tested
+    let err = contract
+        .withdraw_ibc(&mut app, &treasurer, "channel-0", "osmo1treasury", &[])
+        .unwrap_err();
+    assert_eq!(err, ContractError::NoFunds {});
+}
+
+#[test]
+fn withdraw_ibc() {
+    let mut deps = mock_dependencies_with_balance(&[coin(100, ATOM), coin(5, OSMO)]);
+    let env = mock_env();
+    let owner = mock_info("owner", &[]);
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        ExecMsg::AddIbcChannel {
+            channel_id: "channel-0".to_owned(),
+        },
+    )
+    .unwrap();
+
+    let resp = execute(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        ExecMsg::WithdrawIbc {
+            channel_id: "channel-0".to_owned(),
+            remote_address: "osmo1treasury".to_owned(),
+            funds: vec![coin(40, ATOM), coin(10, OSMO)],
+            timeout: Some(60),
+        },
+    )
+    .unwrap();
+
+    let timeout = IbcTimeout::with_timestamp(env.block.time.plus_seconds(60));
+    assert_eq!(
+        resp.messages,
+        vec![
+            SubMsg::new(IbcMsg::Transfer {
+                channel_id: "channel-0".to_owned(),
+                to_address: "osmo1treasury".to_owned(),
+                amount: coin(40, ATOM),
+                timeout: timeout.clone(),
+            }),
+            SubMsg::new(IbcMsg::Transfer {
+                channel_id: "channel-0".to_owned(),
+                to_address: "osmo1treasury".to_owned(),
+                amount: coin(5, OSMO),
+                timeout,
+            }),
+        ]
+    );
+
+    let event = WithdrawIbcEvent::try_from(&resp.events[0]).unwrap();
+    assert_eq!(
+        event,
+        WithdrawIbcEvent {
+            sender: owner.sender.clone(),
+            channel_id: "channel-0".to_owned(),
+            remote_address: "osmo1treasury".to_owned(),
+            amount: vec![coin(40, ATOM), coin(5, OSMO)],
+        }
+    );
+
+    // Transfers are counted as withdrawn when sent, a later timeout isn't tracked
+    let stats: StatsResp =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::Stats {}).unwrap()).unwrap();
+    assert_eq!(stats.total_withdrawn, [coin(40, ATOM), coin(5, OSMO)]);
+
+    let err = execute(
+        deps.as_mut(),
+        env,
+        owner,
+        ExecMsg::WithdrawIbc {
+            channel_id: "channel-1".to_owned(),
+            remote_address: "osmo1treasury".to_owned(),
+            funds: coins(40, ATOM),
+            timeout: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ChannelNotAllowed {
+            channel_id: "channel-1".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn donor_governance() {
+    let owner = Addr::unchecked("owner");
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+    let carol = Addr::unchecked("carol");
+    let receiver = Addr::unchecked("receiver");
+
+    let mut app = App::new(|router, _api, storage| {
+        for (donor, amount) in [(&alice, 60), (&bob, 30), (&carol, 110)] {
+            router
+                .bank
+                .init_balance(storage, donor, coins(amount, ATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            governance: Some(Governance {
+                denom: ATOM.to_owned(),
+                voting_period: Duration::Height(10),
+                quorum: Decimal::percent(50),
+                threshold: Decimal::percent(50),
+            }),
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+
+    contract.donate(&mut app, &alice, &coins(60, ATOM)).unwrap();
+    contract.donate(&mut app, &bob, &coins(30, ATOM)).unwrap();
+    contract.donate(&mut app, &carol, &coins(10, ATOM)).unwrap();
+    app.update_block(|block| block.height += 1);
+
+    let err = contract.withdraw(&mut app, &owner).unwrap_err();
+    assert_eq!(err, ContractError::GovernanceEnabled {});
+
+    contract
+        .spend_proposal(&mut app, &owner, &receiver, &coins(40, ATOM), "Servers")
+        .unwrap();
+
+    // Donations made after the proposal was created don't add weight
+    contract
+        .donate(&mut app, &carol, &coins(100, ATOM))
+        .unwrap();
+
+    let err = contract
+        .vote(&mut app, &owner, 0, VoteOption::Yes)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NoVotingWeight {
+            denom: ATOM.to_owned(),
+        }
+    );
+
+    contract.vote(&mut app, &alice, 0, VoteOption::Yes).unwrap();
+    contract.vote(&mut app, &bob, 0, VoteOption::No).unwrap();
+
+    let err = contract
+        .vote(&mut app, &alice, 0, VoteOption::No)
+        .unwrap_err();
+    assert_eq!(err, ContractError::AlreadyVoted { id: 0 });
+
+    let err = contract.execute_proposal(&mut app, &owner, 0).unwrap_err();
+    assert_eq!(err, ContractError::ProposalNotPassed { id: 0 });
+
+    let resp = contract.query_proposal(&app, 0).unwrap();
+    assert_eq!(
+        resp,
+        ProposalResp {
+            id: 0,
+            proposer: owner.clone(),
+            receiver: receiver.clone(),
+            amount: coins(40, ATOM),
+            description: "Servers".to_owned(),
+            expires: Expiration::AtHeight(app.block_info().height + 10),
+            total_weight: Uint128::new(100),
+            yes: Uint128::new(60),
+            no: Uint128::new(30),
+            abstain: Uint128::zero(),
+            status: ProposalStatus::Open,
+        }
+    );
+
+    app.update_block(|block| block.height += 10);
+
+    let err = contract
+        .vote(&mut app, &carol, 0, VoteOption::No)
+        .unwrap_err();
+    assert_eq!(err, ContractError::VotingClosed { id: 0 });
+
+    let resp = contract.query_proposal(&app, 0).unwrap();
+    assert_eq!(resp.status, ProposalStatus::Passed);
+
+    let event = contract.execute_proposal(&mut app, &bob, 0).unwrap();
+    assert_eq!(
+        event,
+        ExecuteProposalEvent {
+            sender: bob.clone(),
+            proposal_id: 0,
+            receiver: receiver.clone(),
+            amount: coins(40, ATOM),
+        }
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&receiver).unwrap(),
+        coins(40, ATOM)
+    );
+
+    let err = contract.execute_proposal(&mut app, &bob, 0).unwrap_err();
+    assert_eq!(err, ContractError::ProposalNotPassed { id: 0 });
+
+    // Carol's second donation counts now, Bob alone doesn't reach the quorum
+    contract
+        .spend_proposal(&mut app, &owner, &receiver, &coins(20, ATOM), "Ads")
+        .unwrap();
+    contract.vote(&mut app, &bob, 1, VoteOption::Yes).unwrap();
+    app.update_block(|block| block.height += 10);
+
+    let resp = contract.query_proposals(&app, None, None).unwrap();
+    let statuses: Vec<_> = resp
+        .proposals
+        .iter()
+        .map(|proposal| (proposal.id, proposal.total_weight, proposal.status.clone()))
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            (0, Uint128::new(100), ProposalStatus::Executed),
+            (1, Uint128::new(200), ProposalStatus::Rejected),
+        ]
+    );
+
+    let resp = contract.query_votes(&app, 0, None, None).unwrap();
+    assert_eq!(
+        resp,
+        VotesResp {
+            votes: vec![
+                VoteResp {
+                    voter: alice.clone(),
+                    vote: VoteOption::Yes,
+                    weight: Uint128::new(60),
+                },
+                VoteResp {
+                    voter: bob,
+                    vote: VoteOption::No,
+                    weight: Uint128::new(30),
+                },
+            ],
+        }
+    );
+
+    let resp = contract.query_votes(&app, 0, &alice, None).unwrap();
+    assert_eq!(resp.votes.len(), 1);
+}
+
+#[test]
+fn governance_disabled() {
+    let owner = Addr::unchecked("owner");
+    let receiver = Addr::unchecked("receiver");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    let err = contract
+        .spend_proposal(&mut app, &owner, &receiver, &coins(10, ATOM), "Servers")
+        .unwrap_err();
+    assert_eq!(err, ContractError::GovernanceDisabled {});
+
+    let governance = Governance {
+        denom: ATOM.to_owned(),
+        voting_period: Duration::Height(10),
+        quorum: Decimal::zero(),
+        threshold: Decimal::percent(50),
+    };
+    let err = contract
+        .update_config(
+            &mut app,
+            &owner,
+            ConfigUpdate {
+                governance: Some(Some(governance.clone())),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidGovernance {});
+
+    contract
+        .update_config(
+            &mut app,
+            &owner,
+            ConfigUpdate {
+                governance: Some(Some(Governance {
+                    quorum: Decimal::percent(30),
+                    ..governance
+                })),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    let err = contract
+        .withdraw_to(&mut app, &owner, &receiver, None, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::GovernanceEnabled {});
+
+    let err = contract.execute_proposal(&mut app, &owner, 3).unwrap_err();
+    assert_eq!(err, ContractError::ProposalNotFound { id: 3 });
+}
+
+fn approval_policy(approvers: &[&Addr], threshold: u32) -> ApprovalPolicy {
+    ApprovalPolicy {
+        approvers: approvers.iter().map(ToString::to_string).collect(),
+        threshold,
+        limits: coins(100, ATOM),
+        window: Duration::Height(10),
+        expiry: Duration::Height(10),
+    }
+}
+
+#[test]
+fn withdrawal_approvals() {
+    let owner = Addr::unchecked("owner");
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+    let carol = Addr::unchecked("carol");
+    let donor = Addr::unchecked("donor");
+    let receiver = Addr::unchecked("receiver");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &donor, coins(300, ATOM))
+            .unwrap();
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            approval_policy: Some(approval_policy(&[&alice, &bob, &carol], 2)),
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+
+    contract
+        .donate(&mut app, &donor, &coins(300, ATOM))
+        .unwrap();
+
+    // Withdrawals within the limits don't need approvals
+    contract
+        .withdraw_to(&mut app, &owner, &receiver, coins(50, ATOM), None)
+        .unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&receiver).unwrap(),
+        coins(50, ATOM)
+    );
+
+    let event = contract
+        .request_withdrawal(&mut app, &owner, &receiver, None)
+        .unwrap();
+    assert_eq!(
+        event,
+        WithdrawalRequestEvent {
+            sender: owner.clone(),
+            id: 0,
+            target: WithdrawalTarget::Address {
+                receiver: receiver.clone(),
+            },
+            amount: coins(250, ATOM),
+        }
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(contract.addr()).unwrap(),
+        coins(250, ATOM)
+    );
+
+    let err = contract
+        .approve_withdrawal(&mut app, &owner, 0)
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotApprover {});
+
+    let event = contract.approve_withdrawal(&mut app, &alice, 0).unwrap();
+    assert_eq!(
+        event,
+        ApproveWithdrawalEvent {
+            sender: alice.clone(),
+            id: 0,
+            approvals: 1,
+        }
+    );
+    let err = contract
+        .approve_withdrawal(&mut app, &alice, 0)
+        .unwrap_err();
+    assert_eq!(err, ContractError::AlreadyApproved { id: 0 });
+
+    contract.approve_withdrawal(&mut app, &bob, 0).unwrap();
+    contract.revoke_approval(&mut app, &bob, 0).unwrap();
+    let err = contract.revoke_approval(&mut app, &bob, 0).unwrap_err();
+    assert_eq!(err, ContractError::NotApproved { id: 0 });
+
+    let err = contract
+        .execute_withdrawal(&mut app, &receiver, 0)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientApprovals {
+            id: 0,
+            approvals: 1,
+            threshold: 2,
+        }
+    );
+
+    contract.approve_withdrawal(&mut app, &carol, 0).unwrap();
+    let resp = contract.query_pending_withdrawal(&app, 0).unwrap();
+    assert_eq!(
+        resp,
+        PendingWithdrawalResp {
+            id: 0,
+            requester: owner.clone(),
+            target: WithdrawalTarget::Address {
+                receiver: receiver.clone(),
+            },
+            amount: coins(250, ATOM),
+            expires: Expiration::AtHeight(app.block_info().height + 10),
+            approvals: vec![alice.clone(), carol.clone()],
+            status: PendingWithdrawalStatus::Open,
+        }
+    );
+
+    // Anyone can execute an approved withdrawal
+    let event = contract.execute_withdrawal(&mut app, &receiver, 0).unwrap();
+    assert_eq!(
+        event,
+        WithdrawToEvent {
+            sender: receiver.clone(),
+            receiver: receiver.clone(),
+            amount: coins(250, ATOM),
+        }
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&receiver).unwrap(),
+        coins(300, ATOM)
+    );
+
+    let err = contract
+        .execute_withdrawal(&mut app, &receiver, 0)
+        .unwrap_err();
+    assert_eq!(err, ContractError::WithdrawalExecuted { id: 0 });
+    let resp = contract.query_pending_withdrawal(&app, 0).unwrap();
+    assert_eq!(resp.status, PendingWithdrawalStatus::Executed);
+
+    let err = contract
+        .approve_withdrawal(&mut app, &alice, 1)
+        .unwrap_err();
+    assert_eq!(err, ContractError::WithdrawalNotFound { id: 1 });
+}
+
+#[test]
+fn withdrawal_approval_expiry() {
+    let owner = Addr::unchecked("owner");
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+    let donor = Addr::unchecked("donor");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &donor, coins(300, ATOM))
+            .unwrap();
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            approval_policy: Some(approval_policy(&[&alice, &bob], 1)),
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+
+    contract
+        .donate(&mut app, &donor, &coins(300, ATOM))
+        .unwrap();
+    contract
+        .request_withdrawal(&mut app, &owner, &owner, coins(200, ATOM))
+        .unwrap();
+    contract
+        .request_withdrawal(&mut app, &owner, &owner, coins(150, ATOM))
+        .unwrap();
+
+    app.update_block(|block| block.height += 10);
+
+    let err = contract
+        .approve_withdrawal(&mut app, &alice, 0)
+        .unwrap_err();
+    assert_eq!(err, ContractError::WithdrawalExpired { id: 0 });
+
+    let resp = contract
+        .query_pending_withdrawals(&app, None, None)
+        .unwrap();
+    let ids: Vec<_> = resp
+        .withdrawals
+        .iter()
+        .map(|withdrawal| (withdrawal.id, withdrawal.status.clone()))
+        .collect();
+    assert_eq!(
+        ids,
+        [
+            (0, PendingWithdrawalStatus::Expired),
+            (1, PendingWithdrawalStatus::Expired),
+        ]
+    );
+
+    let resp = contract.query_pending_withdrawals(&app, 0, None).unwrap();
+    assert_eq!(resp.withdrawals.len(), 1);
+    assert_eq!(resp.withdrawals[0].id, 1);
+}
+
+#[test]
+fn invalid_approval_policy() {
+    let owner = Addr::unchecked("owner");
+    let alice = Addr::unchecked("alice");
+
+    let mut app = App::default();
+
+    let code_id = CountingContract::store_code(&mut app);
+    let err = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            approval_policy: Some(approval_policy(&[&alice], 2)),
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidApprovalThreshold {
+            threshold: 2,
+            approvers: 1,
+        }
+    );
+
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    let err = contract
+        .update_config(
+            &mut app,
+            &owner,
+            ConfigUpdate {
+                approval_policy: Some(Some(approval_policy(&[&alice, &alice], 1))),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DuplicateApprover {
+            address: alice.to_string(),
+        }
+    );
+}
+
+#[test]
+fn pause_operations() {
+    let owner = Addr::unchecked("owner");
+    let guardian = Addr::unchecked("guardian");
+    let donor = Addr::unchecked("donor");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &donor, coins(30, ATOM))
+            .unwrap();
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    contract
+        .grant_role(&mut app, &owner, Role::Guardian, &guardian)
+        .unwrap();
+
+    let err = contract
+        .pause(&mut app, &donor, Operation::Donations)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MissingRole {
+            role: Role::Guardian
+        }
+    );
+
+    let paused_at = app.block_info().height;
+    let event = contract
+        .pause(&mut app, &guardian, Operation::Donations)
+        .unwrap();
+    assert_eq!(
+        event,
+        PauseEvent {
+            sender: guardian.clone(),
+            operation: Operation::Donations,
+        }
+    );
+    let err = contract
+        .pause(&mut app, &owner, Operation::Donations)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::AlreadyPaused {
+            operation: Operation::Donations
+        }
+    );
+
+    let err = contract
+        .donate(&mut app, &donor, &coins(10, ATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Paused {
+            operation: Operation::Donations
+        }
+    );
+
+    // Other operations keep working
+    contract.reset(&mut app, &owner, 5).unwrap();
+
+    app.update_block(|block| block.height += 1);
+    contract
+        .pause(&mut app, &owner, Operation::Withdrawals)
+        .unwrap();
+    contract.pause(&mut app, &owner, Operation::Resets).unwrap();
+
+    let err = contract.withdraw(&mut app, &owner).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Paused {
+            operation: Operation::Withdrawals
+        }
+    );
+    let err = contract.reset(&mut app, &owner, 0).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Paused {
+            operation: Operation::Resets
+        }
+    );
+
+    let resp = contract.query_status(&app).unwrap();
+    assert_eq!(
+        resp,
+        StatusResp {
+            paused: vec![
+                PausedOperation {
+                    operation: Operation::Donations,
+                    paused_by: guardian.clone(),
+                    since: paused_at,
+                },
+                PausedOperation {
+                    operation: Operation::Withdrawals,
+                    paused_by: owner.clone(),
+                    since: paused_at + 1,
+                },
+                PausedOperation {
+                    operation: Operation::Resets,
+                    paused_by: owner.clone(),
+                    since: paused_at + 1,
+                },
+            ],
+        }
+    );
+
+    contract
+        .unpause(&mut app, &guardian, Operation::Donations)
+        .unwrap();
+    let err = contract
+        .unpause(&mut app, &guardian, Operation::Donations)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NotPaused {
+            operation: Operation::Donations
+        }
+    );
+
+    contract.donate(&mut app, &donor, &coins(10, ATOM)).unwrap();
+    let resp = contract.query_value(&app).unwrap();
+    assert_eq!(resp, ValueResp { value: 6 });
+
+    let resp = contract.query_status(&app).unwrap();
+    assert_eq!(resp.paused.len(), 2);
+}
+
+#[test]
+fn donor_allowlist() {
+    let owner = Addr::unchecked("owner");
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+
+    let mut app = App::new(|router, _api, storage| {
+        for donor in [&alice, &bob] {
+            router
+                .bank
+                .init_balance(storage, donor, coins(20, ATOM))
+                .unwrap();
+        }
+    });
+
+    let token = instantiate_cw20(&mut app, &bob, 20);
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            access_mode: AccessMode::Allowlist,
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+    contract
+        .add_cw20_token(&mut app, &owner, &token, 10)
+        .unwrap();
+
+    let err = contract
+        .add_to_access_list(&mut app, &alice, AccessListKind::Allow, &[&alice])
+        .unwrap_err();
+    assert_eq!(err, ContractError::MissingRole { role: Role::Admin });
+
+    contract
+        .add_to_access_list(&mut app, &owner, AccessListKind::Allow, &[&alice])
+        .unwrap();
+
+    contract.donate(&mut app, &alice, &coins(10, ATOM)).unwrap();
+
+    let err = contract
+        .donate(&mut app, &bob, &coins(10, ATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DonorNotAllowed {
+            donor: bob.to_string()
+        }
+    );
+    let err = contract
+        .donate_cw20(&mut app, &bob, &token, 10)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DonorNotAllowed {
+            donor: bob.to_string()
+        }
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&bob).unwrap(),
+        coins(20, ATOM)
+    );
+    assert_eq!(cw20_balance(&app, &token, &bob), 20);
+
+    contract
+        .remove_from_access_list(&mut app, &owner, AccessListKind::Allow, &[&alice])
+        .unwrap();
+    let err = contract
+        .donate(&mut app, &alice, &coins(10, ATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DonorNotAllowed {
+            donor: alice.to_string()
+        }
+    );
+
+    let resp = contract.query_value(&app).unwrap();
+    assert_eq!(resp, ValueResp { value: 1 });
+}
+
+#[test]
+fn donor_denylist() {
+    let owner = Addr::unchecked("owner");
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+    let carol = Addr::unchecked("carol");
+
+    let mut app = App::new(|router, _api, storage| {
+        for donor in [&alice, &bob] {
+            router
+                .bank
+                .init_balance(storage, donor, coins(20, ATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    contract
+        .add_to_access_list(
+            &mut app,
+            &owner,
+            AccessListKind::Deny,
+            &[&carol, &bob, &alice],
+        )
+        .unwrap();
+
+    // The list is ignored while the contract is open
+    contract.donate(&mut app, &bob, &coins(10, ATOM)).unwrap();
+
+    contract
+        .update_config(
+            &mut app,
+            &owner,
+            ConfigUpdate {
+                access_mode: Some(AccessMode::Denylist),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    contract
+        .remove_from_access_list(&mut app, &owner, AccessListKind::Deny, &[&alice])
+        .unwrap();
+
+    contract.donate(&mut app, &alice, &coins(10, ATOM)).unwrap();
+    let err = contract
+        .donate(&mut app, &bob, &coins(10, ATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DonorNotAllowed {
+            donor: bob.to_string()
+        }
+    );
+
+    let resp = contract
+        .query_access_list(&app, AccessListKind::Deny, None, 1)
+        .unwrap();
+    assert_eq!(
+        resp,
+        AccessListResp {
+            mode: AccessMode::Denylist,
+            addresses: vec![bob.clone()],
+        }
+    );
+    let resp = contract
+        .query_access_list(&app, AccessListKind::Deny, &bob, None)
+        .unwrap();
+    assert_eq!(resp.addresses, [carol]);
+}
+
+#[test]
+fn donation_rate_limit() {
+    let owner = Addr::unchecked("owner");
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+
+    let mut app = App::new(|router, _api, storage| {
+        for donor in [&alice, &bob] {
+            router
+                .bank
+                .init_balance(storage, donor, coins(100, ATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            rate_limit: Some(RateLimit {
+                cooldown: Some(Duration::Height(1)),
+                window: Some(RateWindow {
+                    duration: Duration::Height(10),
+                    max_donations: 2,
+                }),
+                exceeded: RateLimitAction::Reject,
+            }),
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+
+    let start = app.block_info().height;
+    contract.donate(&mut app, &alice, &coins(10, ATOM)).unwrap();
+
+    let err = contract
+        .donate(&mut app, &alice, &coins(10, ATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::RateLimited {
+            retry_after: Expiration::AtHeight(start + 1)
+        }
+    );
+    // Donations which wouldn't count are not limited
+    contract.donate(&mut app, &alice, &coins(5, ATOM)).unwrap();
+
+    app.update_block(|block| block.height += 1);
+    contract.donate(&mut app, &alice, &coins(10, ATOM)).unwrap();
+
+    app.update_block(|block| block.height += 1);
+    let err = contract
+        .donate(&mut app, &alice, &coins(10, ATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::RateLimited {
+            retry_after: Expiration::AtHeight(start + 10)
+        }
+    );
+    contract.donate(&mut app, &bob, &coins(10, ATOM)).unwrap();
+
+    app.update_block(|block| block.height = start + 10);
+    contract.donate(&mut app, &alice, &coins(10, ATOM)).unwrap();
+
+    let resp = contract.query_value(&app).unwrap();
+    assert_eq!(resp, ValueResp { value: 4 });
+    assert_eq!(
+        app.wrap().query_all_balances(&alice).unwrap(),
+        coins(65, ATOM)
+    );
+}
+
+#[test]
+fn donation_rate_limit_uncounted() {
+    let owner = Addr::unchecked("owner");
+    let alice = Addr::unchecked("alice");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &alice, coins(100, ATOM))
+            .unwrap();
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    let err = contract
+        .update_config(
+            &mut app,
+            &owner,
+            ConfigUpdate {
+                rate_limit: Some(Some(RateLimit {
+                    cooldown: None,
+                    window: Some(RateWindow {
+                        duration: Duration::Height(10),
+                        max_donations: 0,
+                    }),
+                    exceeded: RateLimitAction::Reject,
+                })),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidRateLimit {});
+
+    contract
+        .update_config(
+            &mut app,
+            &owner,
+            ConfigUpdate {
+                rate_limit: Some(Some(RateLimit {
+                    cooldown: Some(Duration::Time(60)),
+                    window: None,
+                    exceeded: RateLimitAction::Uncounted,
+                })),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    contract.donate(&mut app, &alice, &coins(10, ATOM)).unwrap();
+    let event = contract.donate(&mut app, &alice, &coins(10, ATOM)).unwrap();
+    assert_eq!(event.counter_after, 1);
+    assert_eq!(
+        app.wrap().query_all_balances(contract.addr()).unwrap(),
+        coins(20, ATOM)
+    );
+
+    app.update_block(|block| block.time = block.time.plus_seconds(60));
+    let event = contract.donate(&mut app, &alice, &coins(10, ATOM)).unwrap();
+    assert_eq!(event.counter_after, 2);
+
+    contract
+        .update_config(
+            &mut app,
+            &owner,
+            ConfigUpdate {
+                rate_limit: Some(None),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let event = contract.donate(&mut app, &alice, &coins(10, ATOM)).unwrap();
+    assert_eq!(event.counter_after, 3);
+}
+
+#[test]
+fn donation_memos() {
+    let owner = Addr::unchecked("owner");
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+
+    let mut app = App::new(|router, _api, storage| {
+        for donor in [&alice, &bob] {
+            router
+                .bank
+                .init_balance(storage, donor, coins(50, ATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            max_memo_length: Some(10),
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+
+    let height = app.block_info().height;
+    contract
+        .donate_with_memo(&mut app, &alice, &coins(10, ATOM), "Thanks!")
+        .unwrap();
+    let err = contract
+        .donate_with_memo(&mut app, &alice, &coins(10, ATOM), "Thanks a lot")
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MemoTooLong {
+            length: 12,
+            max_length: 10,
+        }
+    );
+    contract.donate(&mut app, &bob, &coins(5, ATOM)).unwrap();
+    contract
+        .donate_with_memo(&mut app, &alice, &coins(20, ATOM), "Again")
+        .unwrap();
+
+    let resp = contract.query_donations(&app, None, None).unwrap();
+    assert_eq!(resp.donations.len(), 3);
+    assert_eq!(
+        resp.donations[0],
+        DonationResp {
+            id: 0,
+            donor: alice.clone(),
+            counter_id: DEFAULT_COUNTER.to_owned(),
+            amount: coins(10, ATOM),
+            counted: true,
+            height,
+            memo: Some("Thanks!".to_owned()),
+            memo_hidden: false,
+        }
+    );
+    assert_eq!(resp.donations[1].donor, bob);
+    assert!(!resp.donations[1].counted);
+    assert_eq!(resp.donations[1].memo, None);
+
+    let resp = contract.query_donations(&app, 0, 1).unwrap();
+    let ids: Vec<_> = resp.donations.iter().map(|donation| donation.id).collect();
+    assert_eq!(ids, [1]);
+
+    let resp = contract
+        .query_donations_by(&app, &alice, None, None)
+        .unwrap();
+    let ids: Vec<_> = resp.donations.iter().map(|donation| donation.id).collect();
+    assert_eq!(ids, [0, 2]);
+    let resp = contract.query_donations_by(&app, &alice, 0, None).unwrap();
+    let ids: Vec<_> = resp.donations.iter().map(|donation| donation.id).collect();
+    assert_eq!(ids, [2]);
+
+    let err = contract.hide_memo(&mut app, &alice, 0).unwrap_err();
+    assert_eq!(err, ContractError::MissingRole { role: Role::Admin });
+    let err = contract.hide_memo(&mut app, &owner, 3).unwrap_err();
+    assert_eq!(err, ContractError::DonationNotFound { id: 3 });
+
+    contract.hide_memo(&mut app, &owner, 0).unwrap();
+    let resp = contract
+        .query_donations_by(&app, &alice, None, None)
+        .unwrap();
+    assert_eq!(resp.donations[0].memo, None);
+    assert!(resp.donations[0].memo_hidden);
+    assert_eq!(resp.donations[0].amount, coins(10, ATOM));
+    assert_eq!(resp.donations[1].memo, Some("Again".to_owned()));
+}
+
+#[test]
+fn stats() {
+    let owner = Addr::unchecked("owner");
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+    let receiver = Addr::unchecked("receiver");
+
+    let mut app = App::new(|router, _api, storage| {
+        for donor in [&alice, &bob] {
+            router
+                .bank
+                .init_balance(storage, donor, coins(50, ATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        None,
+        coin(10, ATOM),
+        None,
+    )
+    .unwrap();
+
+    let resp = contract.query_stats(&app).unwrap();
+    assert_eq!(
+        resp,
+        StatsResp {
+            counted_donations: 0,
+            uncounted_donations: 0,
+            unique_donors: 0,
+            total_received: vec![],
+            total_withdrawn: vec![],
+            last_donation_height: None,
+            last_withdrawal_height: None,
+        }
+    );
+
+    contract.donate(&mut app, &alice, &coins(10, ATOM)).unwrap();
+    contract.donate(&mut app, &bob, &coins(5, ATOM)).unwrap();
+    app.update_block(|block| block.height += 1);
+    contract.donate(&mut app, &alice, &coins(20, ATOM)).unwrap();
+    let donation_height = app.block_info().height;
+
+    app.update_block(|block| block.height += 1);
+    contract
+        .withdraw_to(&mut app, &owner, &receiver, coins(15, ATOM), None)
+        .unwrap();
+    app.update_block(|block| block.height += 1);
+    contract.withdraw(&mut app, &owner).unwrap();
+
+    let resp = contract.query_stats(&app).unwrap();
+    assert_eq!(
+        resp,
+        StatsResp {
+            counted_donations: 2,
+            uncounted_donations: 1,
+            unique_donors: 2,
+            total_received: coins(35, ATOM),
+            total_withdrawn: coins(35, ATOM),
+            last_donation_height: Some(donation_height),
+            last_withdrawal_height: Some(app.block_info().height),
+        }
+    );
+}
+
+#[test]
+fn disable_governance() {
+    let owner = Addr::unchecked("owner");
+    let donor = Addr::unchecked("donor");
+    let receiver = Addr::unchecked("receiver");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &donor, coins(20, ATOM))
+            .unwrap();
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            governance: Some(Governance {
+                denom: ATOM.to_owned(),
+                voting_period: Duration::Height(10),
+                quorum: Decimal::percent(50),
+                threshold: Decimal::percent(50),
+            }),
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+
+    contract.donate(&mut app, &donor, &coins(20, ATOM)).unwrap();
+
+    let err = contract
+        .withdraw_to(&mut app, &owner, &receiver, None, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::GovernanceEnabled {});
+
+    let disable = ConfigUpdate {
+        governance: Some(None),
+        ..Default::default()
+    };
+    let err = contract
+        .update_config(&mut app, &donor, disable.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::MissingRole { role: Role::Admin });
+
+    let event = contract.update_config(&mut app, &owner, disable).unwrap();
+    assert_eq!(event.changes[0].field, "governance");
+    assert_eq!(event.changes[0].new, "null");
+    let resp = contract.query_config(&app).unwrap();
+    assert_eq!(resp.governance, None);
+
+    contract
+        .withdraw_to(&mut app, &owner, &receiver, None, None)
+        .unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&receiver).unwrap(),
+        coins(20, ATOM)
+    );
+}
+
+#[test]
+fn distribution_approvals() {
+    let owner = Addr::unchecked("owner");
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+    let donor = Addr::unchecked("donor");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &donor, coins(300, ATOM))
+            .unwrap();
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            approval_policy: Some(approval_policy(&[&alice, &bob], 2)),
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+
+    contract
+        .donate(&mut app, &donor, &coins(300, ATOM))
+        .unwrap();
+    contract
+        .set_beneficiaries(&mut app, &owner, &[(&alice, 5000), (&bob, 5000)], &owner)
+        .unwrap();
+
+    let event = contract.request_distribution(&mut app, &owner).unwrap();
+    assert_eq!(
+        event,
+        WithdrawalRequestEvent {
+            sender: owner.clone(),
+            id: 0,
+            target: WithdrawalTarget::Beneficiaries {},
+            amount: coins(300, ATOM),
+        }
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(contract.addr()).unwrap(),
+        coins(300, ATOM)
+    );
+
+    contract.approve_withdrawal(&mut app, &alice, 0).unwrap();
+    let err = contract
+        .execute_distribution(&mut app, &owner, 0)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientApprovals {
+            id: 0,
+            approvals: 1,
+            threshold: 2,
+        }
+    );
+
+    contract.approve_withdrawal(&mut app, &bob, 0).unwrap();
+    let event = contract.execute_distribution(&mut app, &owner, 0).unwrap();
+    assert_eq!(
+        event,
+        DistributeEvent {
+            sender: owner.clone(),
+            amount: coins(300, ATOM),
+        }
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&alice).unwrap(),
+        coins(150, ATOM)
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&bob).unwrap(),
+        coins(150, ATOM)
+    );
+}
+
+#[test]
+fn withdraw_ibc_approvals() {
+    let mut deps = mock_dependencies_with_balance(&coins(300, ATOM));
+    let mut env = mock_env();
+    let owner = mock_info("owner", &[]);
+    let alice = mock_info("alice", &[]);
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            approval_policy: Some(approval_policy(&[&alice.sender], 1)),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        ExecMsg::AddIbcChannel {
+            channel_id: "channel-0".to_owned(),
+        },
+    )
+    .unwrap();
+
+    let resp = execute(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        ExecMsg::WithdrawIbc {
+            channel_id: "channel-0".to_owned(),
+            remote_address: "osmo1treasury".to_owned(),
+            funds: coins(200, ATOM),
+            timeout: Some(60),
+        },
+    )
+    .unwrap();
+    assert!(resp.messages.is_empty());
+
+    let event = WithdrawalRequestEvent::try_from(&resp.events[0]).unwrap();
+    assert_eq!(
+        event,
+        WithdrawalRequestEvent {
+            sender: owner.sender.clone(),
+            id: 0,
+            target: WithdrawalTarget::Ibc {
+                channel_id: "channel-0".to_owned(),
+                remote_address: "osmo1treasury".to_owned(),
+                timeout: 60,
+            },
+            amount: coins(200, ATOM),
+        }
+    );
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        alice.clone(),
+        ExecMsg::ApproveWithdrawal { id: 0 },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        ExecMsg::RemoveIbcChannel {
+            channel_id: "channel-0".to_owned(),
+        },
+    )
+    .unwrap();
+
+    // The channel has to be still allowed when the withdrawal is executed
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        alice.clone(),
+        ExecMsg::ExecuteWithdrawal { id: 0 },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ChannelNotAllowed {
+            channel_id: "channel-0".to_owned(),
+        }
+    );
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        ExecMsg::AddIbcChannel {
+            channel_id: "channel-0".to_owned(),
+        },
+    )
+    .unwrap();
+
+    // The timeout is counted from the execution
+    env.block.time = env.block.time.plus_seconds(100);
+    let resp = execute(
+        deps.as_mut(),
+        env.clone(),
+        alice.clone(),
+        ExecMsg::ExecuteWithdrawal { id: 0 },
+    )
+    .unwrap();
+    assert_eq!(
+        resp.messages,
+        vec![SubMsg::new(IbcMsg::Transfer {
+            channel_id: "channel-0".to_owned(),
+            to_address: "osmo1treasury".to_owned(),
+            amount: coin(200, ATOM),
+            timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(60)),
+        })]
+    );
+
+    let event = WithdrawIbcEvent::try_from(&resp.events[0]).unwrap();
+    assert_eq!(
+        event,
+        WithdrawIbcEvent {
+            sender: alice.sender,
+            channel_id: "channel-0".to_owned(),
+            remote_address: "osmo1treasury".to_owned(),
+            amount: coins(200, ATOM),
+        }
+    );
+}
+
+#[test]
+fn withdrawal_limit_window() {
+    let owner = Addr::unchecked("owner");
+    let alice = Addr::unchecked("alice");
+    let donor = Addr::unchecked("donor");
+    let receiver = Addr::unchecked("receiver");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &donor, coins(300, ATOM))
+            .unwrap();
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            approval_policy: Some(approval_policy(&[&alice], 1)),
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+
+    contract
+        .donate(&mut app, &donor, &coins(300, ATOM))
+        .unwrap();
+
+    contract
+        .withdraw_to(&mut app, &owner, &receiver, coins(60, ATOM), None)
+        .unwrap();
+
+    // Withdrawals within the limit add up over the window
+    let event = contract
+        .request_withdrawal(&mut app, &owner, &receiver, coins(60, ATOM))
+        .unwrap();
+    assert_eq!(event.id, 0);
+    contract
+        .withdraw_to(&mut app, &owner, &receiver, coins(40, ATOM), None)
+        .unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&receiver).unwrap(),
+        coins(100, ATOM)
+    );
+
+    app.update_block(|block| block.height += 10);
+
+    contract
+        .withdraw_to(&mut app, &owner, &receiver, coins(60, ATOM), None)
+        .unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&receiver).unwrap(),
+        coins(160, ATOM)
+    );
+}
+
+#[test]
+fn access_mode_switch() {
+    let owner = Addr::unchecked("owner");
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+    let carol = Addr::unchecked("carol");
+
+    let mut app = App::new(|router, _api, storage| {
+        for donor in [&alice, &bob, &carol] {
+            router
+                .bank
+                .init_balance(storage, donor, coins(30, ATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            access_mode: AccessMode::Denylist,
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+
+    contract
+        .add_to_access_list(&mut app, &owner, AccessListKind::Deny, &[&bob])
+        .unwrap();
+    contract
+        .add_to_access_list(&mut app, &owner, AccessListKind::Allow, &[&alice])
+        .unwrap();
+
+    // The allowlist is ignored in denylist mode
+    contract.donate(&mut app, &carol, &coins(10, ATOM)).unwrap();
+
+    let mode = |mode| ConfigUpdate {
+        access_mode: Some(mode),
+        ..Default::default()
+    };
+    contract
+        .update_config(&mut app, &owner, mode(AccessMode::Allowlist))
+        .unwrap();
+
+    // Denied addresses don't become the only allowed ones
+    contract.donate(&mut app, &alice, &coins(10, ATOM)).unwrap();
+    for donor in [&bob, &carol] {
+        let err = contract
+            .donate(&mut app, donor, &coins(10, ATOM))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::DonorNotAllowed {
+                donor: donor.to_string()
+            }
+        );
+    }
+
+    contract
+        .update_config(&mut app, &owner, mode(AccessMode::Denylist))
+        .unwrap();
+
+    contract.donate(&mut app, &alice, &coins(10, ATOM)).unwrap();
+    contract.donate(&mut app, &carol, &coins(10, ATOM)).unwrap();
+    let err = contract
+        .donate(&mut app, &bob, &coins(10, ATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DonorNotAllowed {
+            donor: bob.to_string()
+        }
+    );
+
+    let resp = contract
+        .query_access_list(&app, AccessListKind::Allow, None, None)
+        .unwrap();
+    assert_eq!(
+        resp,
+        AccessListResp {
+            mode: AccessMode::Denylist,
+            addresses: vec![alice],
+        }
+    );
+    let resp = contract
+        .query_access_list(&app, AccessListKind::Deny, None, None)
+        .unwrap();
+    assert_eq!(resp.addresses, [bob]);
+}
+
+#[test]
+fn failed_approved_withdrawal() {
+    let owner = Addr::unchecked("owner");
+    let alice = Addr::unchecked("alice");
+    let receiver = Addr::unchecked("receiver");
+
+    let mut app = App::default();
+    let height = app.block_info().height;
+    let token = instantiate_paused_cw20(&mut app, &owner, height + 5);
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            approval_policy: Some(approval_policy(&[&alice], 1)),
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+    contract
+        .add_cw20_token(&mut app, &owner, &token, 10)
+        .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        contract.addr().clone(),
+        &ExecMsg::WithdrawTo {
+            receiver: receiver.to_string(),
+            funds: vec![],
+            cw20_funds: vec![Cw20Coin {
+                address: token.to_string(),
+                amount: Uint128::new(10),
+            }],
+        },
+        &[],
+    )
+    .unwrap();
+    contract.approve_withdrawal(&mut app, &alice, 0).unwrap();
+
+    // A failed payout reverts the execution, keeping the approvals
+    app.execute_contract(
+        alice.clone(),
+        contract.addr().clone(),
+        &ExecMsg::ExecuteWithdrawal { id: 0 },
+        &[],
+    )
+    .unwrap_err();
+    let resp = contract.query_pending_withdrawal(&app, 0).unwrap();
+    assert_eq!(resp.status, PendingWithdrawalStatus::Open);
+    assert_eq!(resp.approvals, std::slice::from_ref(&alice));
+    let resp = contract.query_withdrawals(&app, None, None).unwrap();
+    assert!(resp.withdrawals.is_empty());
+
+    app.update_block(|block| block.height += 5);
+
+    let event = contract.execute_withdrawal(&mut app, &alice, 0).unwrap();
+    assert_eq!(
+        event,
+        WithdrawToEvent {
+            sender: alice,
+            receiver,
+            amount: coins(10, format!("cw20:{token}")),
+        }
+    );
+    let resp = contract.query_pending_withdrawal(&app, 0).unwrap();
+    assert_eq!(resp.status, PendingWithdrawalStatus::Executed);
+    let resp = contract.query_withdrawals(&app, None, None).unwrap();
+    assert_eq!(resp.withdrawals[0].outcome, WithdrawalOutcome::Succeeded {});
+}
+
+#[test]
+fn revoked_requester_withdrawal() {
+    let owner = Addr::unchecked("owner");
+    let alice = Addr::unchecked("alice");
+    let treasurer = Addr::unchecked("treasurer");
+    let donor = Addr::unchecked("donor");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &donor, coins(300, ATOM))
+            .unwrap();
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            approval_policy: Some(approval_policy(&[&alice], 1)),
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+
+    contract
+        .donate(&mut app, &donor, &coins(300, ATOM))
+        .unwrap();
+    contract
+        .grant_role(&mut app, &owner, Role::Treasurer, &treasurer)
+        .unwrap();
+    contract
+        .request_withdrawal(&mut app, &treasurer, &treasurer, None)
+        .unwrap();
+    contract.approve_withdrawal(&mut app, &alice, 0).unwrap();
+
+    contract
+        .revoke_role(&mut app, &owner, Role::Treasurer, &treasurer)
+        .unwrap();
+    let err = contract
+        .execute_withdrawal(&mut app, &alice, 0)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MissingRole {
+            role: Role::Treasurer
+        }
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(contract.addr()).unwrap(),
+        coins(300, ATOM)
+    );
+}
+
+#[test]
+fn clear_approval_policy() {
+    let owner = Addr::unchecked("owner");
+    let alice = Addr::unchecked("alice");
+    let donor = Addr::unchecked("donor");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &donor, coins(300, ATOM))
+            .unwrap();
+    });
+
+    let code_id = CountingContract::store_code(&mut app);
+    let contract = CountingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Counting contract",
+        &InstantiateMsg {
+            minimal_donation: coin(10, ATOM),
+            approval_policy: Some(approval_policy(&[&alice], 1)),
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+
+    contract
+        .donate(&mut app, &donor, &coins(300, ATOM))
+        .unwrap();
+
+    // A missing field keeps the policy
+    contract
+        .update_config(&mut app, &owner, ConfigUpdate::default())
+        .unwrap();
+    let resp = contract.query_config(&app).unwrap();
+    assert!(resp.approval_policy.is_some());
+
+    contract
+        .update_config(
+            &mut app,
+            &owner,
+            ConfigUpdate {
+                approval_policy: Some(None),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let resp = contract.query_config(&app).unwrap();
+    assert_eq!(resp.approval_policy, None);
+
+    contract
+        .withdraw_to(&mut app, &owner, &owner, coins(300, ATOM), None)
+        .unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(300, ATOM)
     );
 }
 
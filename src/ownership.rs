@@ -0,0 +1,54 @@
+use cosmwasm_std::{Addr, BlockInfo, StdResult, Storage};
+
+use crate::error::ContractError;
+use crate::msg::Role;
+use crate::state::{PendingOwner, OWNER, PENDING_OWNER, ROLES};
+
+/// Fails unless `sender` is the current owner.
+pub fn ensure_owner(storage: &dyn Storage, sender: &Addr) -> Result<(), ContractError> {
+    let owner = OWNER
+        .may_load(storage)?
+        .ok_or(ContractError::OwnershipRenounced {})?;
+
+    if *sender != owner {
+        return Err(ContractError::Unauthorized {
+            owner: owner.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Loads the pending transfer, failing if there is none or it is no longer valid at `block`.
+pub fn load_pending(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+) -> Result<PendingOwner, ContractError> {
+    let pending = PENDING_OWNER
+        .may_load(storage)?
+        .ok_or(ContractError::NoPendingOwner {})?;
+
+    if pending.expiry.is_expired(block) {
+        return Err(ContractError::TransferExpired {});
+    }
+
+    Ok(pending)
+}
+
+/// Checks if `address` holds `role`. The current owner implicitly holds every role.
+pub fn has_role(storage: &dyn Storage, role: Role, address: &Addr) -> StdResult<bool> {
+    if OWNER.may_load(storage)?.as_ref() == Some(address) {
+        return Ok(true);
+    }
+
+    Ok(ROLES.has(storage, (role.as_str(), address)))
+}
+
+/// Fails unless `sender` holds `role`.
+pub fn ensure_role(storage: &dyn Storage, role: Role, sender: &Addr) -> Result<(), ContractError> {
+    if !has_role(storage, role, sender)? {
+        return Err(ContractError::MissingRole { role });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use cosmwasm_std::Storage;
+
+use crate::error::ContractError;
+use crate::msg::Operation;
+use crate::state::PAUSED;
+
+/// Fails if `operation` is paused
+pub fn ensure_not_paused(storage: &dyn Storage, operation: Operation) -> Result<(), ContractError> {
+    if PAUSED.has(storage, operation.as_str()) {
+        return Err(ContractError::Paused { operation });
+    }
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use cosmwasm_std::{Addr, BlockInfo, Storage};
+
+use crate::error::ContractError;
+use crate::msg::{RateLimit, RateLimitAction};
+use crate::state::DONATION_RATES;
+
+pub fn validate(limit: &RateLimit) -> Result<(), ContractError> {
+    if limit
+        .window
+        .as_ref()
+        .is_some_and(|window| window.max_donations == 0)
+    {
+        return Err(ContractError::InvalidRateLimit {});
+    }
+    Ok(())
+}
+
+/// Records a donation of `donor` which would be counted. Returns whether it still counts, or
+/// fails if donations over the limit are rejected.
+pub fn record(
+    storage: &mut dyn Storage,
+    limit: Option<&RateLimit>,
+    block: &BlockInfo,
+    donor: &Addr,
+) -> Result<bool, ContractError> {
+    let Some(limit) = limit else {
+        return Ok(true);
+    };
+
+    let mut rate = DONATION_RATES.may_load(storage, donor)?.unwrap_or_default();
+    let cooldown_ends = rate.cooldown_ends.filter(|ends| !ends.is_expired(block));
+    let window_ends = rate.window_ends.filter(|ends| !ends.is_expired(block));
+    let window_full = limit
+        .window
+        .as_ref()
+        .is_some_and(|window| rate.window_donations >= window.max_donations);
+
+    if let Some(retry_after) = cooldown_ends.or(window_ends.filter(|_| window_full)) {
+        return match limit.exceeded {
+            RateLimitAction::Reject => Err(ContractError::RateLimited { retry_after }),
+            RateLimitAction::Uncounted => Ok(false),
+        };
+    }
+
+    rate.cooldown_ends = limit.cooldown.map(|cooldown| cooldown.after(block));
+    match (&limit.window, window_ends) {
+        (Some(_), Some(_)) => rate.window_donations += 1,
+        (Some(window), None) => {
+            rate.window_ends = Some(window.duration.after(block));
+            rate.window_donations = 1;
+        }
+        (None, _) => {
+            rate.window_ends = None;
+            rate.window_donations = 0;
+        }
+    }
+    DONATION_RATES.save(storage, donor, &rate)?;
+
+    Ok(true)
+}
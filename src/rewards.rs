@@ -0,0 +1,74 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Addr, Env, StdResult, Storage, SubMsg, WasmMsg};
+
+use crate::error::ContractError;
+use crate::msg::{Milestones, RewardMetadata};
+use crate::state::{Config, REWARDS_MINTED};
+
+/// Reply id of reward mints, withdrawal replies use the ids of their records counting from 0
+pub const REWARD_REPLY_ID: u64 = u64::MAX;
+
+/// `Mint` message of cw721-base collections using `RewardMetadata` as extension
+#[cw_serde]
+enum Cw721ExecuteMsg {
+    Mint {
+        token_id: String,
+        owner: String,
+        token_uri: Option<String>,
+        extension: RewardMetadata,
+    },
+}
+
+pub fn validate(milestones: &Milestones) -> Result<(), ContractError> {
+    if milestones.every == Some(0) {
+        return Err(ContractError::InvalidMilestones {});
+    }
+    Ok(())
+}
+
+pub fn is_milestone(milestones: &Milestones, counter: u64) -> bool {
+    milestones
+        .every
+        .is_some_and(|every| counter.is_multiple_of(every))
+        || milestones.values.contains(&counter)
+}
+
+/// Mints a reward for `donor` if the counted donation brought the counter to a milestone.
+///
+/// A failed mint doesn't revert the donation, it is only reported on reply.
+pub fn reward_msg(
+    storage: &mut dyn Storage,
+    config: &Config,
+    env: &Env,
+    donor: &Addr,
+    counter_id: &str,
+    counter: u64,
+) -> StdResult<Option<SubMsg>> {
+    let Some(collection) = &config.reward_collection else {
+        return Ok(None);
+    };
+    if !is_milestone(&config.milestones, counter) {
+        return Ok(None);
+    }
+
+    // Counters can be reset, so token ids can't be derived from their values.
+    let token_id = REWARDS_MINTED.may_load(storage)?.unwrap_or_default();
+    REWARDS_MINTED.save(storage, &(token_id + 1))?;
+
+    let msg = WasmMsg::Execute {
+        contract_addr: collection.to_string(),
+        msg: to_json_binary(&Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: donor.to_string(),
+            token_uri: None,
+            extension: RewardMetadata {
+                counter_id: counter_id.to_owned(),
+                counter,
+                height: env.block.height,
+            },
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Some(SubMsg::reply_on_error(msg, REWARD_REPLY_ID)))
+}
@@ -1,6 +1,268 @@
-use cosmwasm_std::{Addr, Coin};
-use cw_storage_plus::Item;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, SnapshotMap, Strategy};
+use cw_utils::Expiration;
 
-pub const COUNTER: Item<u64> = Item::new("counter");
-pub const MINIMAL_DONATION: Item<Coin> = Item::new("minimal_donation");
+use crate::msg::{
+    AccessMode, ApprovalPolicy, Campaign, ChangeCause, Governance, Milestones, RateLimit,
+    RefundPolicy, VoteOption, WithdrawalOutcome, WithdrawalTarget,
+};
+
+#[cw_serde]
+#[derive(Default)]
+pub struct Config {
+    /// Reject donations which would not be counted instead of keeping them
+    pub strict: bool,
+    #[serde(default)]
+    pub refund: RefundPolicy,
+    #[serde(default)]
+    pub reward_collection: Option<Addr>,
+    #[serde(default)]
+    pub milestones: Milestones,
+    /// Funds are spent through donor votes if set
+    #[serde(default)]
+    pub governance: Option<Governance>,
+    #[serde(default)]
+    pub approval_policy: Option<ApprovalPolicy>,
+    #[serde(default)]
+    pub access_mode: AccessMode,
+    #[serde(default)]
+    pub rate_limit: Option<RateLimit>,
+    /// Contracts migrated from older versions don't accept memos until it is set
+    #[serde(default)]
+    pub max_memo_length: u32,
+}
+
+#[cw_serde]
+pub struct CounterState {
+    pub value: u64,
+    /// Donation counted by this counter, the accepted denoms apply if not set
+    pub minimal_donation: Option<Coin>,
+    /// Allowed to reset the counter besides the resetters
+    pub owner: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct CounterChange {
+    pub height: u64,
+    /// Value after the change
+    pub value: u64,
+    pub cause: ChangeCause,
+    /// `None` for changes made by migrations
+    pub sender: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct WithdrawalRecord {
+    pub receiver: Addr,
+    /// Paid out coins, cw20 amounts use the ledger denom
+    pub amount: Vec<Coin>,
+    pub height: u64,
+    pub outcome: WithdrawalOutcome,
+}
+
+#[cw_serde]
+pub struct PendingOwner {
+    pub owner: Addr,
+    pub expiry: Expiration,
+}
+
+#[cw_serde]
+pub struct DonorRecord {
+    /// Total amount donated, per denom
+    pub total: Vec<Coin>,
+    /// Number of donations which incremented the counter
+    pub donations: u64,
+    pub first_donation_height: u64,
+    pub last_donation_height: u64,
+}
+
+/// Share of counted donations forwarded to the collector, managed by the fee admin.
+/// Contracts migrated from 0.1 have no fee.
+#[cw_serde]
+#[derive(Default)]
+pub struct Fee {
+    pub fee_bps: u64,
+    /// Upper bound of `fee_bps`, fixed at instantiation
+    pub max_fee_bps: u64,
+    pub collector: Option<Addr>,
+    /// The fee can't be changed if not set
+    pub admin: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct Donation {
+    pub donor: Addr,
+    pub counter_id: String,
+    pub amount: Vec<Coin>,
+    pub counted: bool,
+    pub height: u64,
+    pub memo: Option<String>,
+    pub memo_hidden: bool,
+}
+
+pub struct DonationIndexes<'a> {
+    pub donor: MultiIndex<'a, Addr, Donation, u64>,
+}
+
+impl<'a> IndexList<Donation> for DonationIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Donation>> + '_> {
+        let v: Vec<&dyn Index<Donation>> = vec![&self.donor];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Log of all donations kept by the contract, keyed by a sequence number
+pub fn donations<'a>() -> IndexedMap<'a, u64, Donation, DonationIndexes<'a>> {
+    let indexes = DonationIndexes {
+        donor: MultiIndex::new(
+            |_, donation| donation.donor.clone(),
+            "donations",
+            "donations__donor",
+        ),
+    };
+    IndexedMap::new("donations", indexes)
+}
+
+/// Totals kept up to date by donations and withdrawals
+#[cw_serde]
+#[derive(Default)]
+pub struct Stats {
+    pub counted_donations: u64,
+    pub uncounted_donations: u64,
+    pub unique_donors: u64,
+    pub total_received: Vec<Coin>,
+    /// Paid out by successful withdrawals, refunds are not included. IBC transfers are counted
+    /// when sent.
+    pub total_withdrawn: Vec<Coin>,
+    pub last_donation_height: Option<u64>,
+    pub last_withdrawal_height: Option<u64>,
+}
+
+/// Counted donations of an address, tracked for the rate limit
+#[cw_serde]
+#[derive(Default)]
+pub struct DonationRate {
+    pub cooldown_ends: Option<Expiration>,
+    pub window_ends: Option<Expiration>,
+    /// Counted donations in the current window
+    pub window_donations: u32,
+}
+
+#[cw_serde]
+pub struct Pause {
+    pub paused_by: Addr,
+    pub height: u64,
+}
+
+/// Withdrawal exceeding the limits of the approval policy
+#[cw_serde]
+pub struct PendingWithdrawal {
+    pub requester: Addr,
+    pub target: WithdrawalTarget,
+    pub amount: Vec<Coin>,
+    pub expires: Expiration,
+    pub approvals: Vec<Addr>,
+    pub executed: bool,
+}
+
+/// Direct withdrawals counted against the approval policy limits
+#[cw_serde]
+pub struct LimitWindow {
+    pub ends: Expiration,
+    pub withdrawn: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct Proposal {
+    pub proposer: Addr,
+    pub receiver: Addr,
+    pub amount: Vec<Coin>,
+    pub description: String,
+    /// Voting weights are taken from the beginning of this block
+    pub start_height: u64,
+    pub expires: Expiration,
+    /// Governance settings when the proposal was created
+    pub governance: Governance,
+    pub total_weight: Uint128,
+    pub yes: Uint128,
+    pub no: Uint128,
+    pub abstain: Uint128,
+    pub executed: bool,
+}
+
+#[cw_serde]
+pub struct Ballot {
+    pub vote: VoteOption,
+    pub weight: Uint128,
+}
+
+/// Receivers of `Distribute` with their weights in basis points
+#[cw_serde]
+pub struct Beneficiaries {
+    pub shares: Vec<(Addr, u64)>,
+    /// Receives the rounding dust
+    pub remainder: Addr,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const COUNTERS: SnapshotMap<&str, CounterState> = SnapshotMap::new(
+    "counters",
+    "counters__checkpoints",
+    "counters__changelog",
+    Strategy::EveryBlock,
+);
+/// Changes of every counter, keyed by counter id and sequence number
+pub const COUNTER_HISTORY: Map<(&str, u64), CounterChange> = Map::new("counter_history");
+/// Single counter of `0.1.x` releases, only read when migrating
+pub const LEGACY_COUNTER: Item<u64> = Item::new("counter");
+/// Single accepted donation of `0.1.x` releases, only read when migrating
+pub const LEGACY_MINIMAL_DONATION: Item<Coin> = Item::new("minimal_donation");
 pub const OWNER: Item<Addr> = Item::new("owner");
+pub const PENDING_OWNER: Item<PendingOwner> = Item::new("pending_owner");
+pub const ROLES: Map<(&str, &Addr), ()> = Map::new("roles");
+pub const DONORS: Map<&Addr, DonorRecord> = Map::new("donors");
+/// Minimal donation counted, per accepted denom
+pub const ACCEPTED_DENOMS: Map<&str, Uint128> = Map::new("accepted_denoms");
+/// Accepted cw20 tokens with their minimal donation
+pub const CW20_TOKENS: Map<&Addr, Uint128> = Map::new("cw20_tokens");
+pub const CAMPAIGN: Item<Campaign> = Item::new("campaign");
+/// Total kept from donations during the campaign, per denom
+pub const CAMPAIGN_RAISED: Map<&str, Uint128> = Map::new("campaign_raised");
+/// Refundable campaign contributions which were not claimed back yet
+pub const CONTRIBUTIONS: Map<&Addr, Vec<Coin>> = Map::new("contributions");
+/// Payouts sent by withdrawals, keyed by the reply id of their submessage
+pub const WITHDRAWALS: Map<u64, WithdrawalRecord> = Map::new("withdrawals");
+/// Number of reward NFTs minted, used as the next token id
+pub const REWARDS_MINTED: Item<u64> = Item::new("rewards_minted");
+pub const BENEFICIARIES: Item<Beneficiaries> = Item::new("beneficiaries");
+pub const FEE: Item<Fee> = Item::new("fee");
+/// Fees forwarded to the collector per denom
+pub const FEES_COLLECTED: Map<&str, Uint128> = Map::new("fees_collected");
+/// Channels allowed for `WithdrawIbc`
+pub const IBC_CHANNELS: Map<&str, ()> = Map::new("ibc_channels");
+pub const PROPOSALS: Map<u64, Proposal> = Map::new("proposals");
+pub const VOTES: Map<(u64, &Addr), Ballot> = Map::new("votes");
+/// Donations kept from each donor per denom, giving weight to their votes
+pub const DONATION_WEIGHTS: SnapshotMap<(&str, &Addr), Uint128> = SnapshotMap::new(
+    "donation_weights",
+    "donation_weights__checkpoints",
+    "donation_weights__changelog",
+    Strategy::EveryBlock,
+);
+pub const DONATION_TOTALS: SnapshotMap<&str, Uint128> = SnapshotMap::new(
+    "donation_totals",
+    "donation_totals__checkpoints",
+    "donation_totals__changelog",
+    Strategy::EveryBlock,
+);
+pub const PENDING_WITHDRAWALS: Map<u64, PendingWithdrawal> = Map::new("pending_withdrawals");
+pub const LIMIT_WINDOW: Item<LimitWindow> = Item::new("limit_window");
+pub const DONATION_RATES: Map<&Addr, DonationRate> = Map::new("donation_rates");
+pub const STATS: Item<Stats> = Item::new("stats");
+/// Only donors allowed in allowlist mode
+pub const ALLOWLIST: Map<&Addr, ()> = Map::new("allowlist");
+/// Donors blocked in denylist mode
+pub const DENYLIST: Map<&Addr, ()> = Map::new("denylist");
+/// Paused operations, keyed by `Operation::as_str`
+pub const PAUSED: Map<&str, Pause> = Map::new("paused");
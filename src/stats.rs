@@ -0,0 +1,37 @@
+use cosmwasm_std::{Coin, StdResult, Storage};
+
+use crate::contract::add_coins;
+use crate::state::{Stats, STATS};
+
+/// Contracts migrated from older versions only have statistics since the migration
+pub fn load(storage: &dyn Storage) -> StdResult<Stats> {
+    STATS.may_load(storage).map(Option::unwrap_or_default)
+}
+
+pub fn record_donation(
+    storage: &mut dyn Storage,
+    height: u64,
+    new_donor: bool,
+    kept: &[Coin],
+    counted: bool,
+) -> StdResult<()> {
+    let mut stats = load(storage)?;
+    if counted {
+        stats.counted_donations += 1;
+    } else {
+        stats.uncounted_donations += 1;
+    }
+    if new_donor {
+        stats.unique_donors += 1;
+    }
+    add_coins(&mut stats.total_received, kept);
+    stats.last_donation_height = Some(height);
+    STATS.save(storage, &stats)
+}
+
+pub fn record_withdrawal(storage: &mut dyn Storage, height: u64, amount: &[Coin]) -> StdResult<()> {
+    let mut stats = load(storage)?;
+    add_coins(&mut stats.total_withdrawn, amount);
+    stats.last_withdrawal_height = Some(height);
+    STATS.save(storage, &stats)
+}